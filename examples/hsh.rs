@@ -21,7 +21,7 @@ use std::str::FromStr;
 ///     // Create new hashes for Argon2i, Bcrypt, and Scrypt
 ///     let password = "password";
 ///     let salt_argon2i: Salt = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
-///     let salt_scrypt: Salt = vec![10, 11, 12, 13, 14, 15, 16, 17, 18, 19];
+///     let salt_scrypt: Salt = (10..26).collect();
 ///     let cost_bcrypt = 16;
 ///
 ///     let hash_argon2i = Hash::new_argon2i(password, salt_argon2i).unwrap();
@@ -44,7 +44,8 @@ fn create_and_verify_hash() {
         Hash::new_argon2i("password", "salt1234".into()).unwrap();
     let hash_bcrypt = Hash::new_bcrypt("password", 16).unwrap();
     let hash_scrypt =
-        Hash::new_scrypt("password", "salt1234".into()).unwrap();
+        Hash::new_scrypt("password", "salt1234salt5678".into())
+            .unwrap();
 
     // Verify these hashes
     verify_password(&hash_argon2i, "password", "Argon2i");
@@ -1,9 +1,31 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::models::hash_algorithm::HashingAlgorithm;
-use argon2rs::argon2i_simple;
+use crate::models::{
+    error::HshError,
+    hash_algorithm::HashingAlgorithm,
+    params::{
+        check_argon2_minimums, ARGON2_VERSION_10, ARGON2_VERSION_13,
+    },
+};
+use argon2::{Algorithm, Argon2, Block, Params, Version};
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// The default Argon2i memory cost, in KiB.
+const DEFAULT_M_COST: u32 = 4096;
+
+/// The default Argon2i time cost, in passes over memory.
+const DEFAULT_T_COST: u32 = 3;
+
+/// The default Argon2i degree of parallelism, in lanes.
+const DEFAULT_P_COST: u32 = 1;
+
+/// The default Argon2i digest length, in bytes.
+const DEFAULT_OUTPUT_LEN: usize = 32;
+
+/// The minimum salt length, in bytes, accepted by Argon2.
+const MIN_SALT_LEN: usize = 8;
 
 /// Implementation of the Argon2i hashing algorithm.
 ///
@@ -41,11 +63,224 @@ impl HashingAlgorithm for Argon2i {
     /// # Returns
     ///
     /// Returns a `Result` with `Ok`, containing the hashed password as a vector of bytes.
-    /// Currently, this function does not handle hashing errors and will always return `Ok`.
+    /// If the salt is shorter than 8 bytes, returns a `String` detailing the error.
     fn hash_password(
         password: &str,
         salt: &str,
     ) -> Result<Vec<u8>, String> {
-        Ok(argon2i_simple(password, salt).into_iter().collect())
+        let mut output = vec![0u8; DEFAULT_OUTPUT_LEN];
+        Self::hash_with_params(
+            password.as_bytes(),
+            salt.as_bytes(),
+            ARGON2_VERSION_13,
+            DEFAULT_M_COST,
+            DEFAULT_T_COST,
+            DEFAULT_P_COST,
+            &mut output,
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(output)
+    }
+}
+
+impl Argon2i {
+    /// Hashes a password using the Argon2i algorithm with explicit cost
+    /// parameters, filling `output` with the derived bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `password`: The plaintext password to be hashed.
+    /// - `salt`: A cryptographic salt of at least 8 bytes.
+    /// - `version`: The Argon2 version, `0x10` or `0x13`.
+    /// - `m_cost`: The memory cost, in KiB.
+    /// - `t_cost`: The time cost, in passes over memory.
+    /// - `p_cost`: The degree of parallelism, in lanes.
+    /// - `output`: The buffer receiving the digest (at least 4 bytes).
+    ///
+    /// # Returns
+    ///
+    /// Returns an `HshError::SaltTooShort` for a salt shorter than 8
+    /// bytes, or an `HshError::InvalidParams` for an unsupported
+    /// version, or parameters or an output length rejected by Argon2.
+    pub fn hash_with_params(
+        password: &[u8],
+        salt: &[u8],
+        version: u32,
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+        output: &mut [u8],
+    ) -> Result<(), HshError> {
+        hash_into(
+            Algorithm::Argon2i,
+            password,
+            salt,
+            version,
+            m_cost,
+            t_cost,
+            p_cost,
+            output,
+        )
+    }
+
+    /// Returns the `argon2::Argon2` context this library would hash
+    /// with, for the given Argon2 parameters and the default 32-byte
+    /// digest.
+    ///
+    /// This is an escape hatch for what the wrapper does not offer.
+    /// The `argon2` type comes from the backend crate and may change
+    /// with any release of this library, without a semver bump.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `HshError::InvalidParams` for parameters of another
+    /// algorithm, an unsupported version, or parameters rejected by
+    /// Argon2.
+    #[cfg(feature = "expose-backends")]
+    pub fn backend(
+        params: crate::models::params::Params,
+    ) -> Result<Argon2<'static>, HshError> {
+        match params {
+            crate::models::params::Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => context(
+                Algorithm::Argon2i,
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+                DEFAULT_OUTPUT_LEN,
+            ),
+            #[allow(unreachable_patterns)]
+            other => Err(HshError::InvalidParams(format!(
+                "an Argon2 backend requires Argon2 parameters, found {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// An Argon2i (or Argon2id) hasher that allocates its memory once and
+/// reuses it for every password it hashes.
+///
+/// The memory is zeroized after each hash, so no state from one
+/// password is left behind when the next is hashed.
+pub(crate) struct ReusableArgon2i {
+    argon2: Argon2<'static>,
+    blocks: Vec<Block>,
+    output_len: usize,
+}
+
+impl ReusableArgon2i {
+    /// Creates a hasher for the given Argon2 variant, version, cost
+    /// parameters and digest length, allocating the memory it needs up
+    /// front.
+    pub(crate) fn with_algorithm(
+        algorithm: Algorithm,
+        version: u32,
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+        output_len: usize,
+    ) -> Result<Self, HshError> {
+        let argon2 = context(
+            algorithm, version, m_cost, t_cost, p_cost, output_len,
+        )?;
+        let blocks =
+            vec![Block::default(); argon2.params().block_count()];
+        Ok(ReusableArgon2i {
+            argon2,
+            blocks,
+            output_len,
+        })
+    }
+
+    /// Hashes a password with the given salt, returning the digest.
+    pub(crate) fn hash(
+        &mut self,
+        password: &[u8],
+        salt: &[u8],
+    ) -> Result<Vec<u8>, HshError> {
+        check_salt(salt)?;
+        let mut output = vec![0u8; self.output_len];
+        let result = self.argon2.hash_password_into_with_memory(
+            password,
+            salt,
+            &mut output,
+            &mut self.blocks,
+        );
+        self.blocks.iter_mut().for_each(Zeroize::zeroize);
+        result.map_err(rejected)?;
+        Ok(output)
+    }
+}
+
+/// Hashes a password with the given Argon2 variant, version and cost
+/// parameters, filling `output` with the derived bytes.
+///
+/// This is shared by the Argon2i and Argon2id implementations, which
+/// differ only in the variant they pass.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn hash_into(
+    algorithm: Algorithm,
+    password: &[u8],
+    salt: &[u8],
+    version: u32,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    output: &mut [u8],
+) -> Result<(), HshError> {
+    check_salt(salt)?;
+    context(algorithm, version, m_cost, t_cost, p_cost, output.len())?
+        .hash_password_into(password, salt, output)
+        .map_err(rejected)
+}
+
+/// Builds the Argon2 context for the given variant, version and
+/// parameters.
+fn context(
+    algorithm: Algorithm,
+    version: u32,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    output_len: usize,
+) -> Result<Argon2<'static>, HshError> {
+    check_argon2_minimums(m_cost, t_cost, p_cost)?;
+    let params = Params::new(m_cost, t_cost, p_cost, Some(output_len))
+        .map_err(rejected)?;
+    let version = match version {
+        ARGON2_VERSION_10 => Version::V0x10,
+        ARGON2_VERSION_13 => Version::V0x13,
+        other => {
+            return Err(HshError::InvalidParams(format!(
+                "Argon2 version {} is not supported",
+                other
+            )))
+        }
+    };
+    Ok(Argon2::new(algorithm, version, params))
+}
+
+/// Checks that a salt is long enough for Argon2.
+fn check_salt(salt: &[u8]) -> Result<(), HshError> {
+    if salt.len() < MIN_SALT_LEN {
+        return Err(HshError::SaltTooShort {
+            min: MIN_SALT_LEN,
+            found: salt.len(),
+        });
     }
+    Ok(())
+}
+
+/// Converts an Argon2 error into an `HshError`.
+///
+/// Argon2 only fails on its inputs (lengths and cost parameters), so
+/// its errors are never transient.
+fn rejected(error: argon2::Error) -> HshError {
+    HshError::InvalidParams(format!("rejected by Argon2: {}", error))
 }
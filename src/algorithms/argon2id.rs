@@ -0,0 +1,121 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::algorithms::argon2i::hash_into;
+use crate::models::{
+    error::HshError, hash_algorithm::HashingAlgorithm,
+    params::ARGON2_VERSION_13,
+};
+use argon2::Algorithm;
+use serde::{Deserialize, Serialize};
+
+/// The default Argon2id memory cost, in KiB.
+const DEFAULT_M_COST: u32 = 4096;
+
+/// The default Argon2id time cost, in passes over memory.
+const DEFAULT_T_COST: u32 = 3;
+
+/// The default Argon2id degree of parallelism, in lanes.
+const DEFAULT_P_COST: u32 = 1;
+
+/// The default Argon2id digest length, in bytes.
+const DEFAULT_OUTPUT_LEN: usize = 32;
+
+/// Implementation of the Argon2id hashing algorithm.
+///
+/// `Argon2id` is the hybrid Argon2 variant recommended by RFC 9106 and
+/// OWASP for password hashing. Its first pass over memory is
+/// data-independent, like Argon2i, which resists side-channel attacks,
+/// and the remaining passes are data-dependent, like Argon2d, which
+/// resists GPU cracking.
+///
+/// This struct implements the `HashingAlgorithm` trait, providing a concrete implementation
+/// for hashing passwords using the Argon2id algorithm.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct Argon2id;
+
+impl HashingAlgorithm for Argon2id {
+    /// Hashes a given password using the Argon2id algorithm.
+    ///
+    /// This method computes a hashed representation of the plaintext `password` using the Argon2id algorithm,
+    /// combined with the provided `salt` for added security.
+    ///
+    /// # Parameters
+    ///
+    /// - `password`: The plaintext password to be hashed.
+    /// - `salt`: A cryptographic salt to prevent rainbow table attacks.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` with `Ok`, containing the hashed password as a vector of bytes.
+    /// If the salt is shorter than 8 bytes, returns a `String` detailing the error.
+    fn hash_password(
+        password: &str,
+        salt: &str,
+    ) -> Result<Vec<u8>, String> {
+        let mut output = vec![0u8; DEFAULT_OUTPUT_LEN];
+        Self::hash_with_params(
+            password.as_bytes(),
+            salt.as_bytes(),
+            ARGON2_VERSION_13,
+            DEFAULT_M_COST,
+            DEFAULT_T_COST,
+            DEFAULT_P_COST,
+            &mut output,
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(output)
+    }
+}
+
+impl Argon2id {
+    /// Hashes a password using the Argon2id algorithm with explicit
+    /// cost parameters, filling `output` with the derived bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `password`: The plaintext password to be hashed.
+    /// - `salt`: A cryptographic salt of at least 8 bytes.
+    /// - `version`: The Argon2 version, `0x10` or `0x13`.
+    /// - `m_cost`: The memory cost, in KiB.
+    /// - `t_cost`: The time cost, in passes over memory.
+    /// - `p_cost`: The degree of parallelism, in lanes.
+    /// - `output`: The buffer receiving the digest (at least 4 bytes).
+    ///
+    /// # Returns
+    ///
+    /// Returns an `HshError::SaltTooShort` for a salt shorter than 8
+    /// bytes, or an `HshError::InvalidParams` for an unsupported
+    /// version, or parameters or an output length rejected by Argon2.
+    pub fn hash_with_params(
+        password: &[u8],
+        salt: &[u8],
+        version: u32,
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+        output: &mut [u8],
+    ) -> Result<(), HshError> {
+        hash_into(
+            Algorithm::Argon2id,
+            password,
+            salt,
+            version,
+            m_cost,
+            t_cost,
+            p_cost,
+            output,
+        )
+    }
+}
@@ -2,10 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 /// The `argon2i` module contains the Argon2i password hashing algorithm.
+#[cfg(feature = "algo-argon2")]
 pub mod argon2i;
 
+/// The `argon2id` module contains the Argon2id password hashing algorithm.
+#[cfg(feature = "algo-argon2")]
+pub mod argon2id;
+
 /// The `bcrypt` module contains the Bcrypt password hashing algorithm.
+#[cfg(feature = "algo-bcrypt")]
 pub mod bcrypt;
 
 /// The `scrypt` module contains the Scrypt password hashing algorithm.
+#[cfg(feature = "algo-scrypt")]
 pub mod scrypt;
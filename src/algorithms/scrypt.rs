@@ -1,11 +1,16 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::models::hash_algorithm::HashingAlgorithm;
+use crate::models::{
+    error::HshError, hash_algorithm::HashingAlgorithm,
+};
 use scrypt::scrypt;
 use scrypt::Params;
 use serde::{Deserialize, Serialize};
 
+/// The default Scrypt digest length, in bytes.
+const DEFAULT_OUTPUT_LEN: usize = 64;
+
 /// Implementation of the Scrypt hashing algorithm.
 ///
 /// `Scrypt` is a struct that represents the Scrypt hashing algorithm,
@@ -51,9 +56,9 @@ impl HashingAlgorithm for Scrypt {
         // computational cost of the hashing process. The parameters used here are chosen
         // to provide a balance between security and performance. Adjust these values based
         // on the security requirements and the expected computational capacity.
-        let params =
-            Params::new(14, 8, 1, 64).map_err(|e| e.to_string())?;
-        let mut output = [0u8; 64];
+        let params = Params::new(14, 8, 1, DEFAULT_OUTPUT_LEN)
+            .map_err(|e| e.to_string())?;
+        let mut output = [0u8; DEFAULT_OUTPUT_LEN];
         scrypt(
             password.as_bytes(),
             salt.as_bytes(),
@@ -64,3 +69,104 @@ impl HashingAlgorithm for Scrypt {
         .map(|_| output.to_vec())
     }
 }
+
+impl Scrypt {
+    /// Hashes a password using the Scrypt algorithm with explicit cost
+    /// parameters, filling `output` with the derived bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `password`: The plaintext password to be hashed.
+    /// - `salt`: A cryptographic salt to prevent rainbow table attacks.
+    /// - `log_n`: The CPU/memory cost, as a base-2 logarithm of `N`.
+    /// - `r`: The block size.
+    /// - `p`: The degree of parallelism.
+    /// - `output`: The buffer receiving the digest (10 to 64 bytes).
+    ///
+    /// # Returns
+    ///
+    /// Returns an `HshError::InvalidParams` if the parameters or output
+    /// length are rejected by Scrypt.
+    pub fn hash_with_params(
+        password: &[u8],
+        salt: &[u8],
+        log_n: u8,
+        r: u32,
+        p: u32,
+        output: &mut [u8],
+    ) -> Result<(), HshError> {
+        let params =
+            Params::new(log_n, r, p, output.len()).map_err(rejected)?;
+        scrypt(password, salt, &params, output).map_err(rejected)
+    }
+
+    /// Returns the `scrypt::Params` this library would hash with, for
+    /// the given Scrypt parameters and the default 64-byte digest.
+    ///
+    /// This is an escape hatch for what the wrapper does not offer.
+    /// The `scrypt` type comes from the backend crate and may change
+    /// with any release of this library, without a semver bump.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `HshError::InvalidParams` for parameters of another
+    /// algorithm, or parameters rejected by Scrypt.
+    #[cfg(feature = "expose-backends")]
+    pub fn backend(
+        params: crate::models::params::Params,
+    ) -> Result<Params, HshError> {
+        match params {
+            crate::models::params::Params::Scrypt { log_n, r, p } => {
+                Params::new(log_n, r, p, DEFAULT_OUTPUT_LEN)
+                    .map_err(rejected)
+            }
+            #[allow(unreachable_patterns)]
+            other => Err(HshError::InvalidParams(format!(
+                "a Scrypt backend requires Scrypt parameters, found {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// A Scrypt hasher that builds its parameters once and reuses them for
+/// every password it hashes.
+pub(crate) struct ReusableScrypt {
+    params: Params,
+    output_len: usize,
+}
+
+impl ReusableScrypt {
+    /// Creates a hasher for the given cost parameters and digest
+    /// length.
+    pub(crate) fn new(
+        log_n: u8,
+        r: u32,
+        p: u32,
+        output_len: usize,
+    ) -> Result<Self, HshError> {
+        let params =
+            Params::new(log_n, r, p, output_len).map_err(rejected)?;
+        Ok(ReusableScrypt { params, output_len })
+    }
+
+    /// Hashes a password with the given salt, returning the digest.
+    pub(crate) fn hash(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+    ) -> Result<Vec<u8>, HshError> {
+        let mut output = vec![0u8; self.output_len];
+        scrypt(password, salt, &self.params, &mut output)
+            .map_err(rejected)?;
+        Ok(output)
+    }
+}
+
+/// Converts a Scrypt error into an `HshError`.
+///
+/// Scrypt only fails on its parameters and output length, so its
+/// errors are never transient.
+fn rejected(error: impl std::fmt::Display) -> HshError {
+    HshError::InvalidParams(format!("rejected by Scrypt: {}", error))
+}
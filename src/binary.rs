@@ -0,0 +1,221 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A compact binary encoding of a `Hash`.
+//!
+//! The layout is, with integers in little-endian order:
+//!
+//! | Field       | Size       | Contents                                  |
+//! |-------------|------------|-------------------------------------------|
+//! | format      | 1 byte     | `FORMAT_VERSION`                          |
+//! | algorithm   | 1 byte     | the `HashAlgorithm` discriminant: `0` Argon2i, `1` Bcrypt, `2` Scrypt, `3` Argon2id |
+//! | params tag  | 1 byte     | `0` none, `1` Argon2, `2` Bcrypt, `3` Scrypt |
+//! | params      | 0-16 bytes | the parameter values, see below           |
+//! | salt length | 4 bytes    | `u32`                                     |
+//! | salt        | variable   | the raw salt                              |
+//! | hash length | 4 bytes    | `u32`                                     |
+//! | hash        | variable   | the raw digest                            |
+//!
+//! Argon2 parameters are written as `version`, `m_cost`, `t_cost` and
+//! `p_cost` (`u32` each), Bcrypt as `cost` (`u32`), and Scrypt as
+//! `log_n` (`u8`), `r` and `p` (`u32` each).
+//!
+//! The format byte comes first so that later layouts can be told apart
+//! from this one. Decoding rejects unknown format versions, truncated
+//! input and trailing bytes.
+
+use crate::models::{
+    error::HshError, hash::Hash, hash_algorithm::HashAlgorithm,
+    params::Params, salt_source::SaltSource,
+};
+
+/// The version of the binary layout written by `encode`.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Encodes a `Hash` in the binary layout.
+///
+/// Returns an `HshError::InvalidParams` if the salt or digest is too
+/// long for its `u32` length prefix.
+///
+/// # Example
+///
+/// ```
+/// use hsh::{binary, models::hash::Hash};
+///
+/// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+/// let bytes = binary::encode(&hash).unwrap();
+///
+/// assert_eq!(bytes[0], binary::FORMAT_VERSION);
+/// assert_eq!(binary::decode(&bytes).unwrap(), hash);
+/// ```
+pub fn encode(hash: &Hash) -> Result<Vec<u8>, HshError> {
+    let mut out = Vec::with_capacity(
+        3 + 16 + 8 + hash.salt.len() + hash.hash.len(),
+    );
+    out.push(FORMAT_VERSION);
+    out.push(hash.algorithm as u8);
+    match hash.params {
+        None => out.push(0),
+        #[cfg(feature = "algo-argon2")]
+        Some(Params::Argon2 {
+            version,
+            m_cost,
+            t_cost,
+            p_cost,
+        }) => {
+            out.push(1);
+            for value in [version, m_cost, t_cost, p_cost] {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        #[cfg(feature = "algo-bcrypt")]
+        Some(Params::Bcrypt { cost }) => {
+            out.push(2);
+            out.extend_from_slice(&cost.to_le_bytes());
+        }
+        #[cfg(feature = "algo-scrypt")]
+        Some(Params::Scrypt { log_n, r, p }) => {
+            out.push(3);
+            out.push(log_n);
+            out.extend_from_slice(&r.to_le_bytes());
+            out.extend_from_slice(&p.to_le_bytes());
+        }
+    }
+    write_bytes(&mut out, "salt", &hash.salt)?;
+    write_bytes(&mut out, "digest", &hash.hash)?;
+    Ok(out)
+}
+
+/// Decodes a `Hash` from the binary layout.
+///
+/// Returns an `HshError::CorruptStoredHash` if the input is truncated,
+/// has trailing bytes, or uses an unknown format version or parameter
+/// tag, and an `HshError::UnsupportedAlgorithm` for an unknown
+/// algorithm byte.
+pub fn decode(bytes: &[u8]) -> Result<Hash, HshError> {
+    let mut reader = Reader { bytes };
+    let format = reader.u8()?;
+    if format != FORMAT_VERSION {
+        return Err(corrupt(format!(
+            "unknown binary format version {}",
+            format
+        )));
+    }
+    let algorithm = match reader.u8()? {
+        #[cfg(feature = "algo-argon2")]
+        0 => HashAlgorithm::Argon2i,
+        #[cfg(feature = "algo-bcrypt")]
+        1 => HashAlgorithm::Bcrypt,
+        #[cfg(feature = "algo-scrypt")]
+        2 => HashAlgorithm::Scrypt,
+        #[cfg(feature = "algo-argon2")]
+        3 => HashAlgorithm::Argon2id,
+        other => {
+            return Err(HshError::UnsupportedAlgorithm(
+                other.to_string(),
+            ))
+        }
+    };
+    let params = match reader.u8()? {
+        0 => None,
+        #[cfg(feature = "algo-argon2")]
+        1 => Some(Params::Argon2 {
+            version: reader.u32()?,
+            m_cost: reader.u32()?,
+            t_cost: reader.u32()?,
+            p_cost: reader.u32()?,
+        }),
+        #[cfg(feature = "algo-bcrypt")]
+        2 => Some(Params::Bcrypt {
+            cost: reader.u32()?,
+        }),
+        #[cfg(feature = "algo-scrypt")]
+        3 => Some(Params::Scrypt {
+            log_n: reader.u8()?,
+            r: reader.u32()?,
+            p: reader.u32()?,
+        }),
+        other => {
+            return Err(corrupt(format!(
+                "unknown parameter tag {}",
+                other
+            )))
+        }
+    };
+    let salt = reader.length_prefixed()?.to_vec();
+    let hash = reader.length_prefixed()?.to_vec();
+    if !reader.bytes.is_empty() {
+        return Err(corrupt(format!(
+            "{} trailing bytes",
+            reader.bytes.len()
+        )));
+    }
+    if let Some(params) = params {
+        params.check_minimums()?;
+    }
+    Ok(Hash {
+        hash,
+        salt,
+        algorithm,
+        params,
+        original_phc: None,
+        salt_source: SaltSource::Unknown,
+    })
+}
+
+/// Appends a `u32` length followed by the bytes themselves, the
+/// `field` named in the error if they are too long.
+fn write_bytes(
+    out: &mut Vec<u8>,
+    field: &str,
+    bytes: &[u8],
+) -> Result<(), HshError> {
+    let len = u32::try_from(bytes.len()).map_err(|_| {
+        HshError::InvalidParams(format!(
+            "{} of {} bytes is too long for the binary format",
+            field,
+            bytes.len()
+        ))
+    })?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+/// Builds the error reported for malformed binary input.
+fn corrupt(reason: String) -> HshError {
+    HshError::CorruptStoredHash(reason)
+}
+
+/// Reads fields from the front of a byte slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], HshError> {
+        if self.bytes.len() < len {
+            return Err(corrupt(String::from(
+                "binary hash is truncated",
+            )));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, HshError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, HshError> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn length_prefixed(&mut self) -> Result<&'a [u8], HshError> {
+        let len = self.u32()?;
+        self.take(len as usize)
+    }
+}
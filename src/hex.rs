@@ -0,0 +1,38 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Hex encoding without data-dependent branches.
+//!
+//! `format!("{:02x}", b)` and lookup tables branch or index on the
+//! encoded bytes. The encoder here computes each digit arithmetically,
+//! so its running time and memory accesses do not depend on secret
+//! data such as a digest.
+
+/// Encodes bytes as lowercase hex, in constant time with respect to
+/// their values.
+///
+/// # Example
+///
+/// ```
+/// use hsh::hex::encode_ct;
+///
+/// assert_eq!(encode_ct(&[0x00, 0x9f, 0xa0, 0xff]), "009fa0ff");
+/// ```
+pub fn encode_ct(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        hex.push(nibble_to_hex(byte >> 4));
+        hex.push(nibble_to_hex(byte & 0x0f));
+    }
+    hex
+}
+
+/// Maps a nibble to its lowercase hex digit without branching.
+///
+/// For nibbles above 9, `(9 - n) >> 8` is all ones, which adds the
+/// distance from `'9' + 1` to `'a'`; otherwise it is zero.
+fn nibble_to_hex(nibble: u8) -> char {
+    let n = i16::from(nibble);
+    let digit = n + i16::from(b'0') + (((9 - n) >> 8) & 0x27);
+    char::from(digit as u8)
+}
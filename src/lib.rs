@@ -27,6 +27,7 @@
 //! The library supports the following Password Hashing Schemes (Password Based Key Derivation Functions):
 //!
 //! - **Argon2i**: A cutting-edge and highly secure key derivation function designed to protect against both traditional brute-force attacks and rainbow table attacks. (Recommended)
+//! - **Argon2id**: The hybrid Argon2 variant, combining the side-channel resistance of Argon2i with the GPU resistance of Argon2d. Recommended for password hashing by RFC 9106 and OWASP. (Recommended)
 //! - **Bcrypt**: A password hashing function designed to be secure against brute-force attacks. It is a work-factor function, which means that it takes a certain amount of time to compute. This makes it difficult to attack with a brute-force algorithm.
 //! - **Scrypt**: A password hashing function designed to be secure against both brute-force attacks and rainbow table attacks. It is a memory-hard and work- factor function, which means that it requires a lot of memory and time to compute. This makes it very difficult to attack with a GPU or other parallel computing device.
 //!
@@ -35,7 +36,7 @@
 //! - **Ease of Use**: Simple API for storing and verifying hashed passwords.
 //! - **Future-Proof**: Quantum-resistant cryptography to secure against future technological advancements.
 //! - **Integrable**: Written in Rust, the library is fast, efficient, and easily integrable into other Rust projects.
-//! - **Versatility**: Supports multiple Password Hashing Schemes like Argon2i, Bcrypt, and Scrypt.
+//! - **Versatility**: Supports multiple Password Hashing Schemes like Argon2i, Argon2id, Bcrypt, and Scrypt.
 //!
 //! ## Core Components
 //!
@@ -43,13 +44,13 @@
 //!
 //! Contains:
 //!
-//! - **algorithm**: Enum representing the hashing algorithm (Argon2i, Bcrypt, Scrypt).
+//! - **algorithm**: Enum representing the hashing algorithm (Argon2i, Argon2id, Bcrypt, Scrypt).
 //! - **hash**: Byte vector containing the hashed password.
 //! - **salt**: Byte vector containing the salt used in hashing.
 //!
 //! ### `HashAlgorithm` Enum
 //!
-//! Provides variants for supported hashing algorithms: Argon2i, Bcrypt, and Scrypt.
+//! Provides variants for supported hashing algorithms: Argon2i, Argon2id, Bcrypt, and Scrypt.
 //!
 //! ## Methods
 //!
@@ -96,7 +97,6 @@
 //! [02]: http://www.apache.org/licenses/LICENSE-2.0
 //! [03]: http://opensource.org/licenses/MIT
 
-#![cfg_attr(feature = "bench", feature(test))]
 #![doc(
     html_favicon_url = "https://kura.pro/hsh/images/favicon.ico",
     html_logo_url = "https://kura.pro/hsh/images/logos/hsh.svg",
@@ -105,15 +105,103 @@
 #![crate_name = "hsh"]
 #![crate_type = "lib"]
 
+#[cfg(not(any(
+    feature = "algo-argon2",
+    feature = "algo-bcrypt",
+    feature = "algo-scrypt"
+)))]
+compile_error!(
+    "at least one of the `algo-argon2`, `algo-bcrypt` and `algo-scrypt` features must be enabled"
+);
+
 /// The `algorithms` module contains the password hashing algorithms.
 pub mod algorithms;
 
+/// The `backends` module re-exports the backend crates, with no
+/// stability guarantee.
+#[cfg(feature = "expose-backends")]
+pub mod backends {
+    /// The Argon2 backend crate.
+    #[cfg(feature = "algo-argon2")]
+    pub use argon2;
+
+    /// The Bcrypt backend crate.
+    #[cfg(feature = "algo-bcrypt")]
+    pub use bcrypt;
+
+    /// The Scrypt backend crate.
+    #[cfg(feature = "algo-scrypt")]
+    pub use scrypt;
+}
+
+/// The `binary` module encodes hashes in a compact binary layout.
+pub mod binary;
+
+/// The `hex` module encodes bytes as hex in constant time.
+pub mod hex;
+
 /// The `macros` module contains functions for generating macros.
 pub mod macros;
 
+/// The `metrics` module counts hash operations.
+mod metrics;
+
 /// The `models` module contains the data models for the library.
 pub mod models;
 
+/// The `phc` module parses and encodes PHC strings.
+pub mod phc;
+
+#[cfg(feature = "prometheus-text")]
+pub use metrics::metrics_text;
+
+use models::{error::HshError, hash::Hash};
+
+/// The shortest password, in bytes, accepted when creating a hash. See
+/// `HashAlgorithm::min_password_len`.
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// The longest password, in bytes, read by `Hash::verify_reader`.
+pub const MAX_PASSWORD_LEN: usize = 4096;
+
+/// The longest stored hash string accepted by `login_verify`.
+const MAX_STORED_HASH_LEN: usize = 512;
+
+/// Verifies a login attempt against a stored PHC string.
+///
+/// This is the recommended entry point for checking a password, and the
+/// only function most applications need. It:
+///
+/// - parses the stored string (`$argon2i$...`, `$scrypt$...` or
+///   `$2b$...`), recovering the salt and parameters it was made with;
+/// - rejects parameters above the limits in `models::params`, so that
+///   a tampered stored hash cannot be used to exhaust memory or CPU;
+/// - recomputes the hash and compares it in constant time;
+/// - never prints or logs the password or the hash.
+///
+/// Returns `Ok(false)` when the password does not match, and an
+/// `HshError` when the stored string cannot be used.
+///
+/// # Example
+///
+/// ```
+/// let stored = "$scrypt$ln=10,r=8,p=1$c29tZXNhbHQ$AgGjc1uwpRUm7ZMofHPj/fmCk9FDtbjURXhD+9G5788";
+///
+/// assert_eq!(hsh::login_verify(stored, "password123"), Ok(true));
+/// assert_eq!(hsh::login_verify(stored, "wrongpassword"), Ok(false));
+/// ```
+pub fn login_verify(
+    stored_phc: &str,
+    password: &str,
+) -> Result<bool, HshError> {
+    if stored_phc.len() > MAX_STORED_HASH_LEN {
+        return Err(HshError::InvalidHashString);
+    }
+    let hash = Hash::parse_phc(stored_phc)?;
+    hash.stored_params()?.check_limits()?;
+    hash.verify(password)
+}
+
 /// This is the main entry point for the `Hash (HSH)` library.
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     if std::env::var("HSH_TEST_MODE").unwrap_or_default() == "1" {
@@ -343,10 +343,14 @@ macro_rules! random_string {
 #[macro_export]
 macro_rules! match_algo {
     ($algo_str:expr) => {
+        // Parsing goes through `FromStr` so that algorithms compiled
+        // out of `hsh` are reported as unsupported.
         match $algo_str {
-            "argon2i" => Ok(HashAlgorithm::Argon2i),
-            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
-            "scrypt" => Ok(HashAlgorithm::Scrypt),
+            "argon2i" | "argon2id" | "bcrypt" | "scrypt" => {
+                $algo_str.parse::<HashAlgorithm>().map_err(|_| {
+                    format!("Unsupported hash algorithm: {}", $algo_str)
+                })
+            }
             _ => Err(format!(
                 "Unsupported hash algorithm: {}",
                 $algo_str
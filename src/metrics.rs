@@ -0,0 +1,90 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Process-wide counters of hash operations.
+//!
+//! The counters are only kept with the `prometheus-text` feature, which
+//! adds `metrics_text` to render them; otherwise recording does
+//! nothing. Only counts are kept, never passwords, salts or digests.
+
+use crate::models::error::HshError;
+#[cfg(feature = "prometheus-text")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of hashes created.
+#[cfg(feature = "prometheus-text")]
+static HASHES: AtomicU64 = AtomicU64::new(0);
+
+/// The number of verifications attempted.
+#[cfg(feature = "prometheus-text")]
+static VERIFICATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of verifications that did not succeed.
+#[cfg(feature = "prometheus-text")]
+static FAILURES: AtomicU64 = AtomicU64::new(0);
+
+/// Records that a hash was created.
+pub(crate) fn record_hash() {
+    #[cfg(feature = "prometheus-text")]
+    let _ = HASHES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records the outcome of a verification. A mismatch and an error both
+/// count as failures.
+#[cfg_attr(not(feature = "prometheus-text"), allow(unused_variables))]
+pub(crate) fn record_verify(result: &Result<bool, HshError>) {
+    #[cfg(feature = "prometheus-text")]
+    {
+        let _ = VERIFICATIONS.fetch_add(1, Ordering::Relaxed);
+        if result != &Ok(true) {
+            let _ = FAILURES.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Renders the counters of this process in the Prometheus text
+/// exposition format, to be served from a `/metrics` endpoint.
+///
+/// - `hsh_hashes_total`: hashes created.
+/// - `hsh_verifications_total`: verifications attempted.
+/// - `hsh_verification_failures_total`: verifications that returned
+///   `Ok(false)` or an error.
+///
+/// # Example
+///
+/// ```
+/// use hsh::models::hash::Hash;
+///
+/// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+/// let _ = hash.verify("password456");
+///
+/// let text = hsh::metrics_text();
+/// assert!(text.contains("# TYPE hsh_hashes_total counter"));
+/// assert!(text.contains("hsh_verification_failures_total "));
+/// ```
+#[cfg(feature = "prometheus-text")]
+pub fn metrics_text() -> String {
+    let counters = [
+        ("hsh_hashes_total", "Password hashes created.", &HASHES),
+        (
+            "hsh_verifications_total",
+            "Password verifications attempted.",
+            &VERIFICATIONS,
+        ),
+        (
+            "hsh_verification_failures_total",
+            "Password verifications that did not succeed.",
+            &FAILURES,
+        ),
+    ];
+    let mut text = String::new();
+    for (name, help, counter) in counters {
+        text.push_str(&format!(
+            "# HELP {0} {1}\n# TYPE {0} counter\n{0} {2}\n",
+            name,
+            help,
+            counter.load(Ordering::Relaxed)
+        ));
+    }
+    text
+}
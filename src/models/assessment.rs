@@ -0,0 +1,111 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{
+    hash_algorithm::{HashAlgorithm, SecurityLevel},
+    params::Params,
+};
+use serde::{Deserialize, Serialize};
+
+/// The lowest Bcrypt cost that is not considered urgently weak.
+pub const MIN_BCRYPT_COST: u32 = 10;
+
+/// The lowest Argon2 memory cost, in KiB, that is not considered
+/// urgently weak. This is the memory cost used by the library defaults.
+pub const MIN_ARGON2_M_COST: u32 = 4096;
+
+/// Represents the result of assessing the strength of a stored hash.
+///
+/// Returned by `Hash::security_assessment`.
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct Assessment {
+    /// The hash algorithm used.
+    pub algorithm: HashAlgorithm,
+    /// The parameters the hash was made with.
+    pub params: Params,
+    /// `true` if the hash is weak enough that it should be replaced at
+    /// the next successful login.
+    pub urgent_rehash: bool,
+    /// A description of each weakness found, empty if none were.
+    pub findings: Vec<String>,
+}
+
+impl Assessment {
+    /// Assesses a hash made with the given algorithm and parameters.
+    ///
+    /// A rehash is urgent for deprecated algorithms, Bcrypt below
+    /// `MIN_BCRYPT_COST` and Argon2 below `MIN_ARGON2_M_COST`.
+    pub fn new(algorithm: HashAlgorithm, params: Params) -> Self {
+        let mut findings = Vec::new();
+        if algorithm.security_level() == SecurityLevel::Deprecated {
+            findings.push(format!(
+                "{} is a deprecated algorithm",
+                algorithm
+            ));
+        }
+        match params {
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } if cost < MIN_BCRYPT_COST => {
+                findings.push(format!(
+                    "Bcrypt cost {} is below the minimum of {}",
+                    cost, MIN_BCRYPT_COST
+                ));
+            }
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 { m_cost, .. }
+                if m_cost < MIN_ARGON2_M_COST =>
+            {
+                findings.push(format!(
+                    "Argon2 memory cost {} KiB is below the minimum of {} KiB",
+                    m_cost, MIN_ARGON2_M_COST
+                ));
+            }
+            _ => {}
+        }
+
+        Assessment {
+            algorithm,
+            params,
+            urgent_rehash: !findings.is_empty(),
+            findings,
+        }
+    }
+}
+
+/// Represents the result of verifying a password and checking the
+/// stored hash in one step.
+///
+/// Returned by `Hash::verify_and_check`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct VerifyOutcome {
+    /// `true` if the password matches the stored hash.
+    pub verified: bool,
+    /// `true` if the stored hash should be replaced at this login, as
+    /// in `Assessment::urgent_rehash`.
+    pub urgent_rehash: bool,
+    /// `true` if the stored hash is `SecurityLevel::Deprecated`, as
+    /// reported by `Hash::security_level`, and should be migrated to
+    /// another algorithm or cost.
+    pub deprecated_algorithm: bool,
+}
@@ -0,0 +1,63 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::collections::HashSet;
+
+/// Represents a set of digests known to belong to compromised
+/// passwords, such as one loaded from a breach corpus.
+///
+/// Hashes are salted, so a stored digest only appears in the set if
+/// that exact hash, with the same salt and parameters, was leaked. A
+/// lookup is therefore best-effort: a match means the hash is known to
+/// be compromised, but no match proves nothing about the password.
+///
+/// # Example
+///
+/// ```
+/// use hsh::models::compromised_filter::CompromisedFilter;
+///
+/// let filter = CompromisedFilter::from_digests([vec![1, 2, 3]]);
+/// assert!(filter.contains(&[1, 2, 3]));
+/// assert!(!filter.contains(&[4, 5, 6]));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CompromisedFilter {
+    digests: HashSet<Vec<u8>>,
+}
+
+impl CompromisedFilter {
+    /// Creates an empty filter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a filter holding the given digests.
+    pub fn from_digests(
+        digests: impl IntoIterator<Item = Vec<u8>>,
+    ) -> Self {
+        CompromisedFilter {
+            digests: digests.into_iter().collect(),
+        }
+    }
+
+    /// Adds a digest to the filter. Returns `false` if it was already
+    /// present.
+    pub fn insert(&mut self, digest: Vec<u8>) -> bool {
+        self.digests.insert(digest)
+    }
+
+    /// Returns `true` if the filter holds the given digest.
+    pub fn contains(&self, digest: &[u8]) -> bool {
+        self.digests.contains(digest)
+    }
+
+    /// Returns the number of digests in the filter.
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    /// Returns `true` if the filter holds no digests.
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::hash_algorithm::HashAlgorithm;
+use std::fmt;
+
+/// Represents the errors that can occur when working with a `Hash`.
+///
+/// Each variant describes a distinct failure so that callers can react
+/// to it programmatically, for example by alerting on a corrupt stored
+/// hash rather than treating it as a failed login.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum HshError {
+    /// The stored hash uses another algorithm than the caller
+    /// expected, as reported by `Hash::verify_expecting`, or holds the
+    /// hash string of another algorithm than its own.
+    AlgorithmMismatch {
+        /// The algorithm the caller expected.
+        expected: HashAlgorithm,
+        /// The algorithm of the stored hash.
+        found: HashAlgorithm,
+    },
+
+    /// The stored hash is structurally broken (bad encoding, wrong
+    /// length, unreadable salt) and can never verify any password.
+    ///
+    /// This is distinct from a password mismatch, which is reported as
+    /// `Ok(false)`.
+    CorruptStoredHash(String),
+
+    /// The underlying hashing backend reported an error.
+    Backend(String),
+
+    /// The hash string does not follow the expected layout.
+    InvalidHashString,
+
+    /// Writing the hash to an output failed.
+    Io(String),
+
+    /// A segment of the hash string is not valid base64.
+    InvalidBase64(String),
+
+    /// The algorithm parameters are malformed or outside the accepted
+    /// limits.
+    InvalidParams(String),
+
+    /// A `HashBuilder` was built without a hash, salt or algorithm.
+    MissingFields,
+
+    /// The password does not match the stored hash, for operations
+    /// that require it to, such as `Hash::set_salt`.
+    PasswordMismatch,
+
+    /// The password is shorter than required.
+    PasswordTooShort {
+        /// The shortest accepted password, in bytes.
+        min: usize,
+    },
+
+    /// The password is longer than allowed.
+    PasswordTooLong {
+        /// The longest accepted password, in bytes.
+        max: usize,
+    },
+
+    /// The salt is shorter than required.
+    SaltTooShort {
+        /// The shortest accepted salt, in bytes.
+        min: usize,
+        /// The length of the given salt, in bytes.
+        found: usize,
+    },
+
+    /// The stored parameters are weaker than the minimum required by
+    /// the caller, as reported by `Hash::verify_with_min_params`.
+    ParamsBelowPolicy(String),
+
+    /// A password given as bytes is not valid UTF-8, but the algorithm
+    /// only accepts UTF-8 passwords (Bcrypt).
+    NonUtf8Password,
+
+    /// The hash algorithm is not supported.
+    UnsupportedAlgorithm(String),
+}
+
+impl fmt::Display for HshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HshError::AlgorithmMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Expected a {} hash, found {}",
+                    expected, found
+                )
+            }
+            HshError::CorruptStoredHash(reason) => {
+                write!(f, "Corrupt stored hash: {}", reason)
+            }
+            HshError::Backend(reason) => write!(f, "{}", reason),
+            HshError::InvalidHashString => {
+                write!(f, "Invalid hash string")
+            }
+            HshError::Io(reason) => write!(f, "I/O error: {}", reason),
+            HshError::InvalidBase64(segment) => {
+                write!(f, "Failed to decode base64: {}", segment)
+            }
+            HshError::InvalidParams(reason) => {
+                write!(f, "Invalid parameters: {}", reason)
+            }
+            HshError::MissingFields => write!(f, "Missing fields"),
+            HshError::PasswordMismatch => {
+                write!(f, "Password does not match the stored hash")
+            }
+            HshError::PasswordTooShort { min } => write!(
+                f,
+                "Password is too short. It must be at least {} characters.",
+                min
+            ),
+            HshError::PasswordTooLong { max } => write!(
+                f,
+                "Password is too long. It must be at most {} characters.",
+                max
+            ),
+            HshError::ParamsBelowPolicy(reason) => {
+                write!(f, "Parameters below policy: {}", reason)
+            }
+            HshError::NonUtf8Password => {
+                write!(f, "Password is not valid UTF-8")
+            }
+            HshError::SaltTooShort { min, found } => write!(
+                f,
+                "Salt is too short: {} bytes, at least {} are required",
+                found, min
+            ),
+            HshError::UnsupportedAlgorithm(algo) => {
+                write!(f, "Unsupported hash algorithm: {}", algo)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HshError {}
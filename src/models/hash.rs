@@ -1,37 +1,266 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::hash_algorithm::HashAlgorithm;
+use super::{
+    assessment::{Assessment, VerifyOutcome},
+    compromised_filter::CompromisedFilter,
+    error::HshError,
+    hash_algorithm::{HashAlgorithm, SecurityLevel, VerifyMethod},
+    hash_options::{global_defaults, HashOptions, Scheme},
+    params::Params,
+    password_policy::PasswordPolicy,
+    prehash::{encode_hex, Prehash},
+    salt_encoding::SaltEncoding,
+    salt_source::{SaltRng, SaltSource},
+    stored_credential::StoredCredential,
+};
 use crate::algorithms;
+use crate::binary;
+use crate::hex;
+use crate::metrics;
 use crate::models::hash_algorithm::HashingAlgorithm;
-use algorithms::{argon2i::Argon2i, bcrypt::Bcrypt, scrypt::Scrypt};
+use crate::phc::{self, PHC_B64};
+#[cfg(feature = "algo-bcrypt")]
+use crate::phc::{decode_bcrypt_salt, BCRYPT_B64};
+#[cfg(feature = "algo-argon2")]
+use algorithms::argon2i::{Argon2i, ReusableArgon2i};
+#[cfg(feature = "algo-argon2")]
+use algorithms::argon2id::Argon2id;
+#[cfg(feature = "algo-bcrypt")]
+use algorithms::bcrypt::Bcrypt;
+#[cfg(feature = "algo-scrypt")]
+use algorithms::scrypt::{ReusableScrypt, Scrypt};
 use serde::{Deserialize, Serialize};
 
 // use algorithms::{argon2i::Argon2i, bcrypt::Bcrypt, scrypt::Scrypt};
-use argon2rs::argon2i_simple;
-use base64::{engine::general_purpose, Engine as _};
-// use models::{hash::*, hash_algorithm::*};
-use scrypt::scrypt;
-use std::{fmt, str::FromStr};
+#[cfg(any(feature = "algo-bcrypt", feature = "algo-scrypt"))]
+use base64::engine::general_purpose;
+use base64::Engine as _;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::HashSet,
+    fmt,
+    hash::Hasher,
+    io::{Read, Write},
+    str::FromStr,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "unicode")]
+use unicode_normalization::UnicodeNormalization;
 use vrd::random::Random;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 /// A type alias for a salt.
 pub type Salt = Vec<u8>;
 
+/// The digest length, in bytes, of Argon2i hashes made with the
+/// default parameters.
+#[cfg(feature = "algo-argon2")]
+const ARGON2I_DEFAULT_HASH_LEN: usize = 32;
+
+/// The range of digest lengths, in bytes, accepted for Argon2i.
+#[cfg(feature = "algo-argon2")]
+const ARGON2I_HASH_LEN_RANGE: std::ops::RangeInclusive<usize> = 12..=64;
+
+/// The range of digest lengths, in bytes, accepted for Scrypt.
+#[cfg(feature = "algo-scrypt")]
+const SCRYPT_HASH_LEN_RANGE: std::ops::RangeInclusive<usize> = 10..=64;
+
+/// The shortest salt, in bytes, accepted by `Hash::new_scrypt`.
+#[cfg(feature = "algo-scrypt")]
+pub const SCRYPT_MIN_SALT_LEN: usize = 16;
+
+/// The version of the serialized form written by `Hash`'s `Serialize`
+/// implementation.
+///
+/// Version `0` is the unversioned form written by earlier releases,
+/// whose hashes may lack parameters. Deserialization migrates such
+/// data and rejects versions newer than this one.
+pub const SERDE_VERSION: u32 = 1;
+
+/// The log target of the audit records written by
+/// `Hash::verify_with_subject`.
+pub const AUDIT_LOG_TARGET: &str = "hsh::audit";
+
+/// How often `Hash::verify_with_heartbeat` calls its heartbeat while
+/// verification runs.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The pause before the first retry of `Hash::verify_with_retry`. Each
+/// further retry waits one more multiple of it.
+pub const RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Logs a warning about the limitations of Bcrypt when it is used to
+/// create a new hash. Only the algorithm is named, never the password.
+#[cfg(all(feature = "warn-weak", feature = "algo-bcrypt"))]
+fn warn_bcrypt_limitations() {
+    log::warn!(
+        "Bcrypt only uses the first 72 bytes of a password and stops at \
+         the first NUL byte; consider Argon2 for new hashes"
+    );
+}
+
+/// Returns the salt source of a new hash created with a caller's salt.
+/// Bcrypt ignores that salt and generates its own.
+fn provided_salt_source(algorithm: HashAlgorithm) -> SaltSource {
+    match algorithm {
+        #[cfg(feature = "algo-bcrypt")]
+        HashAlgorithm::Bcrypt => {
+            SaltSource::Generated(SaltRng::Backend)
+        }
+        #[allow(unreachable_patterns)]
+        _ => SaltSource::Provided,
+    }
+}
+
+/// Checks the password of a new hash against `policy`, and its salt
+/// against the minimum of `algorithm`.
+fn check_minimums(
+    algorithm: HashAlgorithm,
+    policy: &PasswordPolicy,
+    password: &[u8],
+    salt: &[u8],
+) -> Result<(), HshError> {
+    policy.check(password)?;
+    if salt.len() < algorithm.min_salt_len() {
+        return Err(HshError::SaltTooShort {
+            min: algorithm.min_salt_len(),
+            found: salt.len(),
+        });
+    }
+    Ok(())
+}
+
+/// The signature shared by `Argon2i::hash_with_params` and
+/// `Argon2id::hash_with_params`.
+#[cfg(feature = "algo-argon2")]
+type Argon2HashFn = fn(
+    &[u8],
+    &[u8],
+    u32,
+    u32,
+    u32,
+    u32,
+    &mut [u8],
+) -> Result<(), HshError>;
+
+/// Returns the function hashing with Argon2 parameters for the given
+/// Argon2 variant.
+#[cfg(feature = "algo-argon2")]
+fn argon2_hash_with_params(algorithm: HashAlgorithm) -> Argon2HashFn {
+    match algorithm {
+        HashAlgorithm::Argon2id => Argon2id::hash_with_params,
+        _ => Argon2i::hash_with_params,
+    }
+}
+
+/// Checks that `digest` is a hash value `algorithm` can produce: a
+/// digest of a supported length for Argon2 and Scrypt, or a modular
+/// crypt string for Bcrypt.
+fn check_digest(
+    algorithm: HashAlgorithm,
+    digest: &[u8],
+) -> Result<(), HshError> {
+    match algorithm {
+        #[cfg(feature = "algo-argon2")]
+        HashAlgorithm::Argon2i | HashAlgorithm::Argon2id => {
+            check_digest_len(algorithm, digest, ARGON2I_HASH_LEN_RANGE)
+        }
+        #[cfg(feature = "algo-scrypt")]
+        HashAlgorithm::Scrypt => {
+            check_digest_len(algorithm, digest, SCRYPT_HASH_LEN_RANGE)
+        }
+        #[cfg(feature = "algo-bcrypt")]
+        HashAlgorithm::Bcrypt => {
+            let parsed = std::str::from_utf8(digest)
+                .map_err(|_| HshError::InvalidHashString)
+                .and_then(phc::parse)?;
+            if parsed.algorithm != HashAlgorithm::Bcrypt {
+                return Err(HshError::InvalidHashString);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Checks that a raw digest's length lies in `range`.
+#[cfg(any(feature = "algo-argon2", feature = "algo-scrypt"))]
+fn check_digest_len(
+    algorithm: HashAlgorithm,
+    digest: &[u8],
+    range: std::ops::RangeInclusive<usize>,
+) -> Result<(), HshError> {
+    if !range.contains(&digest.len()) {
+        return Err(HshError::InvalidParams(format!(
+            "{} hash must be {} to {} bytes, found {}",
+            algorithm,
+            range.start(),
+            range.end(),
+            digest.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Reads a password from `reader` into a buffer that is zeroized when
+/// dropped, rejecting input longer than `MAX_PASSWORD_LEN` bytes.
+///
+/// The buffer is allocated once, with room for the whole limit, so
+/// that no copy of the password is left behind by a reallocation.
+fn read_password(
+    reader: impl Read,
+) -> Result<Zeroizing<Vec<u8>>, HshError> {
+    let mut password =
+        Zeroizing::new(Vec::with_capacity(crate::MAX_PASSWORD_LEN + 1));
+    let len = reader
+        .take(crate::MAX_PASSWORD_LEN as u64 + 1)
+        .read_to_end(&mut password)
+        .map_err(|e| HshError::Io(e.to_string()))?;
+    if len > crate::MAX_PASSWORD_LEN {
+        return Err(HshError::InvalidParams(format!(
+            "Password exceeds the limit of {} bytes",
+            crate::MAX_PASSWORD_LEN
+        )));
+    }
+    Ok(password)
+}
+
+/// Returns `true` if a password looks like a PHC or modular crypt
+/// string, such as `$argon2i$...` or `$2b$...`, which usually means an
+/// existing hash is being hashed again.
+fn looks_like_phc(password: &str) -> bool {
+    let mut segments = password.split('$');
+    let leading = segments.next();
+    let tag = segments.next().unwrap_or_default();
+    leading == Some("")
+        && !tag.is_empty()
+        && tag.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        && segments.next().is_some()
+}
+
 /// A struct for storing and verifying hashed passwords.
 /// It uses `#[non_exhaustive]` and derive macros for common functionalities.
+///
+/// Hashes compare, order and hash by their digest, salt, algorithm and
+/// parameters only; `original_phc` and `salt_source` are metadata.
+///
+/// Hashes serialize as a `version` (`SERDE_VERSION`) followed by their
+/// `hash`, `salt`, `algorithm` and `params`. For algorithms whose hash
+/// embeds the salt (Bcrypt), `salt` is left out when it merely
+/// duplicates the embedded one, and is decoded from the hash again on
+/// deserialization.
+///
+/// Data without a `version` is read as version `0` and migrated: a
+/// missing `params` is filled with `Params::legacy`, the parameters
+/// such hashes were made with.
 #[non_exhaustive]
-#[derive(
-    Clone,
-    Debug,
-    Eq,
-    Hash,
-    Ord,
-    PartialEq,
-    PartialOrd,
-    Serialize,
-    Deserialize,
-)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "HashFields", into = "HashFields")]
 pub struct Hash {
     /// The password hash.
     pub hash: Vec<u8>,
@@ -39,11 +268,176 @@ pub struct Hash {
     pub salt: Salt,
     /// The hash algorithm used.
     pub algorithm: HashAlgorithm,
+    /// The algorithm parameters used for hashing, or `None` if they
+    /// were not recorded (`Params::legacy` is then assumed).
+    pub params: Option<Params>,
+    /// The string the hash was parsed from by `from_string_preserving`,
+    /// returned by `original_phc`. Never serialized.
+    pub(crate) original_phc: Option<String>,
+    /// Where the salt came from, returned by `salt_source`. Never
+    /// serialized.
+    pub(crate) salt_source: SaltSource,
+}
+
+/// The serialized form of a `Hash`.
+#[derive(Serialize, Deserialize)]
+struct HashFields {
+    #[serde(default)]
+    version: u32,
+    hash: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    salt: Option<Salt>,
+    algorithm: HashAlgorithm,
+    #[serde(default)]
+    params: Option<Params>,
+}
+
+/// The bincode form of a `Hash`: the fields of `HashFields`, all
+/// written, as bincode cannot skip fields.
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+struct BincodeFields {
+    version: u32,
+    hash: Vec<u8>,
+    salt: Option<Salt>,
+    algorithm: HashAlgorithm,
+    params: Option<Params>,
+}
+
+#[cfg(feature = "bincode")]
+impl From<HashFields> for BincodeFields {
+    fn from(fields: HashFields) -> Self {
+        BincodeFields {
+            version: fields.version,
+            hash: fields.hash,
+            salt: fields.salt,
+            algorithm: fields.algorithm,
+            params: fields.params,
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl From<BincodeFields> for HashFields {
+    fn from(fields: BincodeFields) -> Self {
+        HashFields {
+            version: fields.version,
+            hash: fields.hash,
+            salt: fields.salt,
+            algorithm: fields.algorithm,
+            params: fields.params,
+        }
+    }
+}
+
+impl From<Hash> for HashFields {
+    fn from(mut hash: Hash) -> Self {
+        let duplicate = hash.algorithm.embeds_salt()
+            && hash.salt == embedded_salt(hash.algorithm, &hash.hash);
+        // `Hash` wipes its buffers on drop, so they are taken rather
+        // than moved out.
+        HashFields {
+            version: SERDE_VERSION,
+            salt: (!duplicate).then(|| std::mem::take(&mut hash.salt)),
+            hash: std::mem::take(&mut hash.hash),
+            algorithm: hash.algorithm,
+            params: hash.params,
+        }
+    }
+}
+
+impl TryFrom<HashFields> for Hash {
+    type Error = HshError;
+
+    fn try_from(fields: HashFields) -> Result<Self, HshError> {
+        let params = match fields.version {
+            0 => fields
+                .params
+                .or_else(|| Some(Params::legacy(fields.algorithm))),
+            SERDE_VERSION => fields.params,
+            other => {
+                return Err(HshError::CorruptStoredHash(format!(
+                    "unknown serialization version {}",
+                    other
+                )))
+            }
+        };
+        if let Some(params) = params {
+            params.check_minimums()?;
+        }
+        let salt = fields.salt.unwrap_or_else(|| {
+            embedded_salt(fields.algorithm, &fields.hash)
+        });
+        Ok(Hash {
+            hash: fields.hash,
+            salt,
+            algorithm: fields.algorithm,
+            params,
+            original_phc: None,
+            salt_source: SaltSource::Unknown,
+        })
+    }
+}
+
+/// Decodes the salt embedded in a stored hash, or returns an empty
+/// salt if the algorithm does not embed one or it cannot be decoded.
+#[cfg_attr(not(feature = "algo-bcrypt"), allow(unused_variables))]
+fn embedded_salt(algorithm: HashAlgorithm, hash: &[u8]) -> Salt {
+    match algorithm {
+        #[cfg(feature = "algo-bcrypt")]
+        HashAlgorithm::Bcrypt => std::str::from_utf8(hash)
+            .ok()
+            .and_then(|mcf| mcf.rsplit('$').next())
+            .and_then(|segment| decode_bcrypt_salt(segment).ok())
+            .unwrap_or_default(),
+        #[allow(unreachable_patterns)]
+        _ => Salt::new(),
+    }
+}
+
+/// Wipes the digest and salt when a `Hash` is dropped, so that no key
+/// material lingers in freed memory.
+impl Drop for Hash {
+    fn drop(&mut self) {
+        self.hash.zeroize();
+        self.salt.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Hash {}
+
+impl PartialEq for Hash {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for Hash {}
+
+impl PartialOrd for Hash {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hash {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.identity().cmp(&other.identity())
+    }
+}
+
+impl std::hash::Hash for Hash {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
 }
 
 impl Hash {
     /// Creates a new `Hash` instance using Argon2i algorithm for password hashing.
     ///
+    /// This is `new_argon2i_with_params` with the library defaults
+    /// (`Params::default_for`) and a 32-byte digest.
+    ///
     /// # Example
     ///
     /// ```
@@ -58,382 +452,3413 @@ impl Hash {
     ///     Err(e) => println!("An error occurred: {}", e),
     /// }
     /// ```
+    #[cfg(feature = "algo-argon2")]
     pub fn new_argon2i(
         password: &str,
         salt: Salt,
-    ) -> Result<Self, String> {
-        // Convert the Vec<u8> salt to a &str
-        let salt_str = std::str::from_utf8(&salt)
-            .map_err(|_| "Failed to convert salt to string")?;
+    ) -> Result<Self, HshError> {
+        match Params::default_for(HashAlgorithm::Argon2i) {
+            Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => Self::new_argon2i_with_params(
+                password,
+                salt,
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+                ARGON2I_DEFAULT_HASH_LEN,
+            ),
+            #[allow(unreachable_patterns)]
+            other => Err(HshError::InvalidParams(format!(
+                "unexpected Argon2i default parameters: {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Creates a new `Hash` instance like `new_argon2i`, padding a salt
+    /// shorter than the 8 bytes Argon2i requires.
+    ///
+    /// The salt is extended with zero bytes up to 8 bytes, and the
+    /// padded salt is stored on the hash, so it verifies as usual.
+    /// Padding adds no entropy: a short salt stays as guessable as it
+    /// was. This is meant for tests and demos with salts such as
+    /// `"salt"`; a warning is logged whenever a salt is padded.
+    /// `new_argon2i` itself keeps rejecting short salts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_argon2i_padded("my_password", b"salt".to_vec()).unwrap();
+    /// assert_eq!(hash.salt(), b"salt\0\0\0\0");
+    /// assert_eq!(hash.verify("my_password"), Ok(true));
+    /// ```
+    #[cfg(feature = "algo-argon2")]
+    pub fn new_argon2i_padded(
+        password: &str,
+        mut salt: Salt,
+    ) -> Result<Self, HshError> {
+        let min_salt_len = HashAlgorithm::Argon2i.min_salt_len();
+        if salt.len() < min_salt_len {
+            log::warn!(
+                "Padding a {}-byte Argon2i salt to {} bytes; padding adds \
+                 no entropy and is only meant for tests and demos",
+                salt.len(),
+                min_salt_len
+            );
+            salt.resize(min_salt_len, 0);
+        }
+        Self::new_argon2i(password, salt)
+    }
+
+    /// Creates a new `Hash` instance using Argon2i algorithm with
+    /// explicit parameters and tag length.
+    ///
+    /// `version` is `0x10` or `0x13`, `m_cost` the memory cost in KiB,
+    /// `t_cost` the number of passes, `p_cost` the degree of
+    /// parallelism and `tag_len` the digest length in bytes (12 to 64).
+    /// PHC strings carry the tag length implicitly, as the length of
+    /// the digest segment, so such hashes round-trip through
+    /// `to_phc_string` and `from_string` and verify with the same
+    /// length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_argon2i_with_params(
+    ///     "my_password",
+    ///     b"somesalt".to_vec(),
+    ///     0x13,
+    ///     256,
+    ///     2,
+    ///     1,
+    ///     24,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(hash.hash_length(), 24);
+    /// assert!(hash.verify("my_password").unwrap());
+    /// ```
+    #[cfg(feature = "algo-argon2")]
+    pub fn new_argon2i_with_params(
+        password: &str,
+        salt: Salt,
+        version: u32,
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+        tag_len: usize,
+    ) -> Result<Self, HshError> {
+        check_minimums(
+            HashAlgorithm::Argon2i,
+            &PasswordPolicy::default(),
+            password.as_bytes(),
+            &salt,
+        )?;
+        if !ARGON2I_HASH_LEN_RANGE.contains(&tag_len) {
+            return Err(HshError::InvalidParams(format!(
+                "Argon2i tag length must be {} to {} bytes, found {}",
+                ARGON2I_HASH_LEN_RANGE.start(),
+                ARGON2I_HASH_LEN_RANGE.end(),
+                tag_len
+            )));
+        }
 
-        // Perform Argon2i hashing
-        let calculated_hash =
-            argon2i_simple(password, salt_str).to_vec();
+        let mut calculated_hash = vec![0u8; tag_len];
+        Argon2i::hash_with_params(
+            password.as_bytes(),
+            &salt,
+            version,
+            m_cost,
+            t_cost,
+            p_cost,
+            &mut calculated_hash,
+        )?;
 
+        metrics::record_hash();
         HashBuilder::new()
             .hash(calculated_hash)
             .salt(salt)
             .algorithm(HashAlgorithm::Argon2i)
+            .params(Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            })
+            .salt_source(SaltSource::Provided)
+            .build()
+    }
+
+    /// Creates a new `Hash` instance using Bcrypt algorithm for password hashing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let password = "my_password";
+    /// let cost: u32 = 16;
+    ///
+    /// let result = Hash::new_bcrypt(password, cost);
+    /// match result {
+    ///     Ok(hash) => println!("Successfully created Bcrypt hash"),
+    ///     Err(e) => println!("An error occurred: {}", e),
+    /// }
+    /// ```
+    #[cfg(feature = "algo-bcrypt")]
+    pub fn new_bcrypt(
+        password: &str,
+        cost: u32,
+    ) -> Result<Self, HshError> {
+        check_minimums(
+            HashAlgorithm::Bcrypt,
+            &PasswordPolicy::default(),
+            password.as_bytes(),
+            &[],
+        )?;
+
+        #[cfg(feature = "warn-weak")]
+        warn_bcrypt_limitations();
+
+        // Perform Bcrypt hashing
+        let hashed_password =
+            bcrypt::hash(password, cost).map_err(|e| match e {
+                bcrypt::BcryptError::CostNotAllowed(_) => {
+                    HshError::InvalidParams(e.to_string())
+                }
+                _ => HshError::Backend(format!(
+                    "Failed to hash password with Bcrypt: {}",
+                    e
+                )),
+            })?;
+
+        // In Bcrypt, the salt is embedded in the hashed password, so
+        // it is decoded from there to be returned by `salt()`.
+        let salt = hashed_password
+            .rsplit('$')
+            .next()
+            .ok_or(HshError::InvalidHashString)
+            .and_then(decode_bcrypt_salt)?;
+
+        metrics::record_hash();
+        HashBuilder::new()
+            .hash(hashed_password.as_bytes().to_vec())
+            .salt(salt)
+            .algorithm(HashAlgorithm::Bcrypt)
+            .params(Params::Bcrypt { cost })
+            .salt_source(SaltSource::Generated(SaltRng::Backend))
+            .build()
+    }
+
+    /// Creates a new `Hash` instance using Scrypt algorithm for password hashing.
+    ///
+    /// The salt must be at least `SCRYPT_MIN_SALT_LEN` (16) bytes long,
+    /// otherwise `HshError::SaltTooShort` is returned. Use
+    /// `new_scrypt_with_min_salt_len` to require another length, or
+    /// `new_scrypt_unchecked` to recreate hashes with existing shorter
+    /// salts. Other failures are reported as `HshError::InvalidParams`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::{Hash, Salt};
+    ///
+    /// let password = "my_password";
+    /// let salt: Salt = (0..16).collect();
+    ///
+    /// let result = Hash::new_scrypt(password, salt);
+    /// match result {
+    ///     Ok(hash) => println!("Successfully created Scrypt hash"),
+    ///     Err(e) => println!("An error occurred: {}", e),
+    /// }
+    /// ```
+    #[cfg(feature = "algo-scrypt")]
+    pub fn new_scrypt(
+        password: &str,
+        salt: Salt,
+    ) -> Result<Self, HshError> {
+        Self::new_scrypt_with_min_salt_len(
+            password,
+            salt,
+            SCRYPT_MIN_SALT_LEN,
+        )
+    }
+
+    /// Creates a new `Hash` instance like `new_scrypt`, requiring a salt
+    /// of at least `min_salt_len` bytes.
+    ///
+    /// The 8 byte minimum of every Scrypt hash still applies to smaller
+    /// values.
+    #[cfg(feature = "algo-scrypt")]
+    pub fn new_scrypt_with_min_salt_len(
+        password: &str,
+        salt: Salt,
+        min_salt_len: usize,
+    ) -> Result<Self, HshError> {
+        if salt.len() < min_salt_len {
+            return Err(HshError::SaltTooShort {
+                min: min_salt_len,
+                found: salt.len(),
+            });
+        }
+        Self::new_scrypt_unchecked(password, salt)
+    }
+
+    /// Creates a new `Hash` instance like `new_scrypt`, without the
+    /// `SCRYPT_MIN_SALT_LEN` check.
+    ///
+    /// This is meant for migrating existing hashes whose salts are
+    /// shorter; new hashes should use `new_scrypt`. Only the 8 byte
+    /// minimum of every Scrypt hash is enforced.
+    #[cfg(feature = "algo-scrypt")]
+    pub fn new_scrypt_unchecked(
+        password: &str,
+        salt: Salt,
+    ) -> Result<Self, HshError> {
+        Self::new_scrypt_with_params(password, salt, 14, 8, 1, 64)
+    }
+
+    /// Creates a new `Hash` instance using Scrypt algorithm with explicit
+    /// parameters.
+    ///
+    /// `log_n` is the base-2 logarithm of the CPU/memory cost `N`, `r`
+    /// the block size, `p` the degree of parallelism and `dklen` the
+    /// digest length in bytes (10 to 64). The parameters are stored on
+    /// the returned `Hash`, so it verifies and encodes as a PHC string
+    /// without further configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_scrypt_with_params(
+    ///     "my_password",
+    ///     b"somesalt".to_vec(),
+    ///     10,
+    ///     8,
+    ///     1,
+    ///     32,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(hash.hash_length(), 32);
+    /// assert!(hash.verify("my_password").unwrap());
+    /// ```
+    #[cfg(feature = "algo-scrypt")]
+    pub fn new_scrypt_with_params(
+        password: &str,
+        salt: Salt,
+        log_n: u8,
+        r: u32,
+        p: u32,
+        dklen: usize,
+    ) -> Result<Self, HshError> {
+        check_minimums(
+            HashAlgorithm::Scrypt,
+            &PasswordPolicy::default(),
+            password.as_bytes(),
+            &salt,
+        )?;
+        if !SCRYPT_HASH_LEN_RANGE.contains(&dklen) {
+            return Err(HshError::InvalidParams(format!(
+                "Scrypt digest length must be {} to {} bytes, found {}",
+                SCRYPT_HASH_LEN_RANGE.start(),
+                SCRYPT_HASH_LEN_RANGE.end(),
+                dklen
+            )));
+        }
+
+        let mut calculated_hash = vec![0u8; dklen];
+        Scrypt::hash_with_params(
+            password.as_bytes(),
+            &salt,
+            log_n,
+            r,
+            p,
+            &mut calculated_hash,
+        )?;
+
+        metrics::record_hash();
+        // Use the builder pattern to construct the Hash instance
+        HashBuilder::new()
+            .hash(calculated_hash)
+            .salt(salt)
+            .algorithm(HashAlgorithm::Scrypt)
+            .params(Params::Scrypt { log_n, r, p })
+            .salt_source(SaltSource::Provided)
             .build()
     }
 
-    /// Creates a new `Hash` instance using Bcrypt algorithm for password hashing.
+    /// Creates a new `Hash` instance from a password given as bytes,
+    /// using the default parameters of the algorithm.
+    ///
+    /// Argon2i and Scrypt hash the bytes as they are. Bcrypt only
+    /// accepts UTF-8 passwords, as with `new_bcrypt`, and generates its
+    /// own salt; other passwords are rejected with
+    /// `HshError::NonUtf8Password`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{error::HshError, hash::Hash, hash_algorithm::HashAlgorithm};
+    ///
+    /// let password = [0xff, 0xfe, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05];
+    /// let hash = Hash::new_bytes(&password, b"somesalt".to_vec(), HashAlgorithm::Argon2i)
+    ///     .unwrap();
+    /// assert_eq!(hash.verify_bytes(&password), Ok(true));
+    ///
+    /// assert_eq!(
+    ///     Hash::new_bytes(&password, Vec::new(), HashAlgorithm::Bcrypt),
+    ///     Err(HshError::NonUtf8Password)
+    /// );
+    /// ```
+    pub fn new_bytes(
+        password: &[u8],
+        salt: Salt,
+        algorithm: HashAlgorithm,
+    ) -> Result<Self, HshError> {
+        Self::new_with_params(
+            password,
+            salt,
+            algorithm,
+            Params::default_for(algorithm),
+            &PasswordPolicy::default(),
+        )
+    }
+
+    /// Creates a new `Hash` instance from a password read from
+    /// `reader`, such as a key file or a passphrase arriving in chunks,
+    /// without going through a `String`.
+    ///
+    /// None of the algorithms can hash their input incrementally, so
+    /// the reader is read to its end into an internal buffer, which is
+    /// zeroized once the hash is made. As with `verify_reader`, input
+    /// longer than `MAX_PASSWORD_LEN` bytes is rejected with
+    /// `HshError::InvalidParams` and read errors are reported as
+    /// `HshError::Io`. The bytes are then hashed as with `new_bytes`,
+    /// so hashes made from UTF-8 input match those of `new`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    /// use std::io::Cursor;
+    ///
+    /// let hash =
+    ///     Hash::new_from_reader(Cursor::new("password123"), "somesalt", "scrypt")
+    ///         .unwrap();
+    ///
+    /// assert_eq!(hash, Hash::new("password123", "somesalt", "scrypt").unwrap());
+    /// assert_eq!(hash.verify_reader(Cursor::new("password123")), Ok(true));
+    /// ```
+    pub fn new_from_reader(
+        reader: impl Read,
+        salt: &str,
+        algo: &str,
+    ) -> Result<Self, HshError> {
+        let algorithm =
+            HashAlgorithm::from_str(algo).map_err(|_| {
+                HshError::UnsupportedAlgorithm(algo.to_string())
+            })?;
+        let password = read_password(reader)?;
+        Self::new_bytes(&password, salt.as_bytes().to_vec(), algorithm)
+    }
+
+    /// Creates a new `Hash` instance like `new`, after decoding the salt
+    /// string under the given encoding.
+    ///
+    /// The decoded bytes are both hashed and stored, so `verify` uses
+    /// the same salt as the hash was made with. This suits salts from
+    /// `generate_salt`, whose Bcrypt and Scrypt salts are base64
+    /// encoded: with `SaltEncoding::Base64`, a Scrypt salt is hashed
+    /// as its 32 random bytes rather than its 44 characters. The hash
+    /// is then made as with `new_bytes`.
+    ///
+    /// A salt that does not decode is rejected with
+    /// `HshError::InvalidParams`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, salt_encoding::SaltEncoding};
+    ///
+    /// let salt = Hash::generate_salt("scrypt").unwrap();
+    /// let hash =
+    ///     Hash::new_with_salt_encoding("password123", &salt, "scrypt", SaltEncoding::Base64)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(hash.salt().len(), 32);
+    /// assert_eq!(hash.verify("password123"), Ok(true));
+    /// ```
+    pub fn new_with_salt_encoding(
+        password: &str,
+        salt: &str,
+        algo: &str,
+        encoding: SaltEncoding,
+    ) -> Result<Self, HshError> {
+        let algorithm =
+            HashAlgorithm::from_str(algo).map_err(|_| {
+                HshError::UnsupportedAlgorithm(algo.to_string())
+            })?;
+        let salt = encoding.decode(salt.as_bytes()).map_err(|_| {
+            HshError::InvalidParams(format!(
+                "salt is not valid {:?}",
+                encoding
+            ))
+        })?;
+        Self::new_bytes(password.as_bytes(), salt, algorithm)
+    }
+
+    /// Creates Argon2i or Argon2id hashes for a sequence of passwords
+    /// and salts, reusing a single Argon2 memory allocation for all of
+    /// them.
+    ///
+    /// This suits batch jobs, such as onboarding many users, where
+    /// allocating the Argon2 memory once per password would dominate.
+    /// The memory is zeroized between items, and hashes are computed
+    /// lazily as the iterator is consumed. Digests have the default
+    /// Argon2 length of 32 bytes.
+    ///
+    /// `options` must be for Argon2i or Argon2id, with `Params::Argon2`;
+    /// otherwise every item yields an `HshError::InvalidParams`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{
+    ///     hash::Hash, hash_algorithm::HashAlgorithm, hash_options::HashOptions,
+    ///     params::Params,
+    /// };
+    ///
+    /// let options = HashOptions::new(
+    ///     HashAlgorithm::Argon2id,
+    ///     Params::Argon2 {
+    ///         version: 0x13,
+    ///         m_cost: 64,
+    ///         t_cost: 1,
+    ///         p_cost: 1,
+    ///     },
+    /// )
+    /// .unwrap();
+    /// let users = vec![("password123", "somesalt"), ("password456", "othersalt")];
+    ///
+    /// let hashes: Vec<Hash> = Hash::hash_sequential(users, options)
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(hashes[1].algorithm(), HashAlgorithm::Argon2id);
+    /// assert!(hashes[1].verify("password456").unwrap());
+    /// ```
+    #[cfg(feature = "algo-argon2")]
+    pub fn hash_sequential<I, P, S>(
+        items: I,
+        options: HashOptions,
+    ) -> impl Iterator<Item = Result<Hash, HshError>>
+    where
+        I: IntoIterator<Item = (P, S)>,
+        P: AsRef<str>,
+        S: AsRef<[u8]>,
+    {
+        let HashOptions { algorithm, params } = options;
+        let variant = match algorithm {
+            HashAlgorithm::Argon2i => Some(argon2::Algorithm::Argon2i),
+            HashAlgorithm::Argon2id => {
+                Some(argon2::Algorithm::Argon2id)
+            }
+            #[allow(unreachable_patterns)]
+            _ => None,
+        };
+        let mut hasher = match (variant, params) {
+            (
+                Some(variant),
+                Params::Argon2 {
+                    version,
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                },
+            ) => ReusableArgon2i::with_algorithm(
+                variant,
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+                ARGON2I_DEFAULT_HASH_LEN,
+            ),
+            _ => Err(HshError::InvalidParams(format!(
+                "sequential hashing requires Argon2 options, found {} with {:?}",
+                algorithm, params
+            ))),
+        };
+
+        items.into_iter().map(move |(password, salt)| {
+            let hasher = hasher.as_mut().map_err(|e| e.clone())?;
+            let salt = salt.as_ref().to_vec();
+            check_minimums(
+                algorithm,
+                &PasswordPolicy::default(),
+                password.as_ref().as_bytes(),
+                &salt,
+            )?;
+            let hash =
+                hasher.hash(password.as_ref().as_bytes(), &salt)?;
+            metrics::record_hash();
+            Ok(Hash {
+                hash,
+                salt,
+                algorithm,
+                params: Some(params),
+                original_phc: None,
+                salt_source: SaltSource::Provided,
+            })
+        })
+    }
+
+    /// A function that returns the hash algorithm used by the hash map.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// A function that returns the parameters of a hash object.
+    ///
+    /// These are the recorded parameters or, for hashes stored without
+    /// them, `Params::legacy`. For Bcrypt, the cost embedded in the hash
+    /// takes precedence. If the recorded parameters belong to another
+    /// algorithm, `Params::legacy` is returned too; `verify` reports
+    /// such hashes as corrupt.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, params::Params};
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    ///
+    /// match hash.parameters() {
+    ///     Params::Bcrypt { cost } => assert_eq!(cost, 4),
+    ///     other => panic!("unexpected parameters: {:?}", other),
+    /// }
+    /// ```
+    pub fn parameters(&self) -> Params {
+        #[cfg(feature = "algo-bcrypt")]
+        if self.algorithm == HashAlgorithm::Bcrypt {
+            let embedded = std::str::from_utf8(&self.hash)
+                .ok()
+                .and_then(|mcf| phc::parse(mcf).ok())
+                .and_then(|hash| hash.params);
+            if let Some(params) = embedded {
+                return params;
+            }
+        }
+        self.stored_params()
+            .unwrap_or_else(|_| Params::legacy(self.algorithm))
+    }
+
+    /// A function that returns the cost of a Bcrypt hash, or `None` for
+    /// hashes of other algorithms.
+    ///
+    /// As with `parameters`, the cost is read from the stored
+    /// `$2b$<cost>$...` string, which is what verification uses, so it
+    /// is reported for hashes made with `new` (`bcrypt::DEFAULT_COST`)
+    /// and `new_bcrypt` alike. Compare it with the cost hashes should
+    /// have to decide whether to re-hash after a successful login.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    /// assert_eq!(hash.bcrypt_cost(), Some(4));
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// assert_eq!(hash.bcrypt_cost(), None);
+    /// ```
+    pub fn bcrypt_cost(&self) -> Option<u32> {
+        match self.parameters() {
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } => Some(cost),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// A function that returns `true` if the hash was made with weaker
+    /// parameters than `target`, and should be replaced by a new hash
+    /// of the password.
+    ///
+    /// The parameters reported by `parameters` are compared with
+    /// `Params::check_at_least`: each Argon2, Bcrypt or Scrypt cost on
+    /// its own, the Bcrypt cost being read from the stored string. An
+    /// older Argon2 version also needs a rehash, and so do parameters
+    /// of another algorithm than `target`.
+    ///
+    /// Call this right after a successful `verify`, when the password
+    /// is at hand to hash it again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, params::Params};
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    ///
+    /// assert!(hash.needs_rehash(&Params::Bcrypt { cost: 5 }));
+    /// assert!(!hash.needs_rehash(&Params::Bcrypt { cost: 4 }));
+    /// ```
+    pub fn needs_rehash(&self, target: &Params) -> bool {
+        let params = self.parameters();
+        match (params, *target) {
+            #[cfg(feature = "algo-argon2")]
+            (
+                Params::Argon2 { version, .. },
+                Params::Argon2 {
+                    version: target_version,
+                    ..
+                },
+            ) if version < target_version => true,
+            _ => params.check_at_least(target).is_err(),
+        }
+    }
+
+    /// A function that returns the algorithm and parameters of a hash
+    /// object, without its salt or digest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, hash_algorithm::HashAlgorithm};
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    /// let scheme = hash.scheme();
+    ///
+    /// assert_eq!(scheme.algorithm, HashAlgorithm::Bcrypt);
+    /// assert!(!scheme.is_expensive());
+    /// ```
+    pub fn scheme(&self) -> Scheme {
+        HashOptions {
+            algorithm: self.algorithm,
+            params: self.parameters(),
+        }
+    }
+
+    /// A function that assesses the strength of a hash object.
+    ///
+    /// The returned `Assessment` flags an urgent rehash for legacy
+    /// algorithms, Bcrypt costs below 10 and Argon2 memory costs below
+    /// the library default, listing each weakness found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 6).unwrap();
+    /// let assessment = hash.security_assessment();
+    ///
+    /// assert!(assessment.urgent_rehash);
+    /// ```
+    pub fn security_assessment(&self) -> Assessment {
+        Assessment::new(self.algorithm, self.parameters())
+    }
+
+    /// A function that returns how suitable a hash object is to keep,
+    /// taking its parameters into account.
+    ///
+    /// This is `HashAlgorithm::security_level`, except that Bcrypt
+    /// hashes with a cost below `MIN_BCRYPT_COST` are `Deprecated`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, hash_algorithm::SecurityLevel};
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    /// assert_eq!(hash.security_level(), SecurityLevel::Deprecated);
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// assert_eq!(hash.security_level(), SecurityLevel::Recommended);
+    /// ```
+    pub fn security_level(&self) -> SecurityLevel {
+        match self.parameters() {
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost }
+                if cost < super::assessment::MIN_BCRYPT_COST =>
+            {
+                SecurityLevel::Deprecated
+            }
+            _ => self.algorithm.security_level(),
+        }
+    }
+
+    /// A function that creates a new hash object from a hash value and a hash algorithm.
+    ///
+    /// The salt and parameters are not recorded, so Argon2 and Scrypt
+    /// hashes created this way cannot verify; use `reconstruct` to
+    /// rebuild a hash from separately stored components.
+    pub fn from_hash(
+        hash: &[u8],
+        algo: &str,
+    ) -> Result<Self, HshError> {
+        let algorithm =
+            HashAlgorithm::from_str(algo).map_err(|_| {
+                HshError::UnsupportedAlgorithm(algo.to_string())
+            })?;
+
+        Ok(Hash {
+            salt: Vec::new(),
+            hash: hash.to_vec(),
+            algorithm,
+            params: None,
+            original_phc: None,
+            salt_source: SaltSource::Unknown,
+        })
+    }
+
+    /// A function that rebuilds a hash object from its separately
+    /// stored digest, salt, algorithm and parameters.
+    ///
+    /// Unlike `from_hash`, the returned hash verifies like the original.
+    /// For Bcrypt, `digest` is the whole `$2b$...` string. Returns
+    /// `Err(HshError::InvalidParams)` if `params` do not belong to
+    /// `algo` or are too small to hash with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let original = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let rebuilt = Hash::reconstruct(
+    ///     original.hash(),
+    ///     original.salt(),
+    ///     original.algorithm(),
+    ///     original.parameters(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(rebuilt, original);
+    /// assert_eq!(rebuilt.verify("password123"), Ok(true));
+    /// ```
+    pub fn reconstruct(
+        digest: &[u8],
+        salt: &[u8],
+        algo: HashAlgorithm,
+        params: Params,
+    ) -> Result<Self, HshError> {
+        if !params.matches(algo) {
+            return Err(HshError::InvalidParams(format!(
+                "parameters do not belong to {}",
+                algo
+            )));
+        }
+        params.check_minimums()?;
+        Ok(Hash {
+            hash: digest.to_vec(),
+            salt: salt.to_vec(),
+            algorithm: algo,
+            params: Some(params),
+            original_phc: None,
+            salt_source: SaltSource::Unknown,
+        })
+    }
+
+    /// A function that creates a new hash object from a PHC string.
+    ///
+    /// See the `phc` module for the layouts that are understood. The
+    /// decoded salt and the parameters are stored on the returned
+    /// `Hash` so that it can be verified directly.
+    pub fn from_string(hash_str: &str) -> Result<Self, HshError> {
+        Self::parse_phc(hash_str)
+    }
+
+    /// A function that creates a new hash object from a PHC string
+    /// like `from_string`, keeping the string itself.
+    ///
+    /// `to_phc_string` re-encodes the parsed fields, which may not
+    /// byte-match the input: base64 written with padding, for
+    /// example, comes back without it. Systems
+    /// that treat the stored string as an opaque token can get the
+    /// exact input back from `original_phc`.
+    ///
+    /// The string is ignored when hashes are compared. It is dropped
+    /// by `set_hash`, `set_password` and `set_salt`, and is never
+    /// serialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// // The salt is base64 with padding, which `to_phc_string` omits.
+    /// let stored = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ=$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+    /// let hash = Hash::from_string_preserving(stored).unwrap();
+    ///
+    /// assert_eq!(hash.original_phc(), Some(stored));
+    /// assert_ne!(hash.to_phc_string().unwrap(), stored);
+    /// ```
+    pub fn from_string_preserving(
+        hash_str: &str,
+    ) -> Result<Self, HshError> {
+        let mut hash = Self::from_string(hash_str)?;
+        hash.original_phc = Some(hash_str.to_string());
+        Ok(hash)
+    }
+
+    /// A function that returns where the salt of the hash came from.
+    ///
+    /// This is recorded when the hash is created, as a debugging aid;
+    /// see `SaltSource`.
+    pub fn salt_source(&self) -> SaltSource {
+        self.salt_source
+    }
+
+    /// Returns the fields compared by `PartialEq`, `Ord` and
+    /// `std::hash::Hash`.
+    fn identity(
+        &self,
+    ) -> (&[u8], &[u8], HashAlgorithm, Option<Params>) {
+        (&self.hash, &self.salt, self.algorithm, self.params)
+    }
+
+    /// A function that returns the string the hash was parsed from by
+    /// `from_string_preserving`, or `None` for any other hash.
+    pub fn original_phc(&self) -> Option<&str> {
+        self.original_phc.as_deref()
+    }
+
+    /// Parses a PHC string into a `Hash`, reporting failures as an
+    /// `HshError`.
+    pub(crate) fn parse_phc(hash_str: &str) -> Result<Self, HshError> {
+        phc::parse(hash_str)
+    }
+
+    /// A function that generates a hash value for a password using the specified hash algorithm.
+    /// The function takes three arguments:
+    ///
+    /// - password: The password to be hashed.
+    /// - salt: A random string used to make the hash value unique.
+    /// - algo: The name of the hash algorithm to use.
+    ///
+    /// The function returns a `Result` object containing the hash value if successful, or an `HshError` if unsuccessful.
+    /// A salt shorter than the algorithm's minimum is reported as an
+    /// `HshError::SaltTooShort` before any hashing.
+    pub fn generate_hash(
+        password: &str,
+        salt: &str,
+        algo: &str,
+    ) -> Result<Vec<u8>, HshError> {
+        if let Ok(algorithm) = HashAlgorithm::from_str(algo) {
+            if salt.len() < algorithm.min_salt_len() {
+                return Err(HshError::SaltTooShort {
+                    min: algorithm.min_salt_len(),
+                    found: salt.len(),
+                });
+            }
+        }
+        match normalize_algorithm(algo).as_str() {
+            #[cfg(feature = "algo-argon2")]
+            "argon2i" => Argon2i::hash_password(password, salt),
+            #[cfg(feature = "algo-argon2")]
+            "argon2id" => Argon2id::hash_password(password, salt),
+            #[cfg(feature = "algo-bcrypt")]
+            "bcrypt" => Bcrypt::hash_password(password, salt),
+            #[cfg(feature = "algo-scrypt")]
+            "scrypt" => Scrypt::hash_password(password, salt),
+            _ => {
+                return Err(HshError::UnsupportedAlgorithm(
+                    algo.to_string(),
+                ))
+            }
+        }
+        .map_err(HshError::Backend)
+    }
+
+    /// A function that generates a random string of the specified length.
+    pub fn generate_random_string(len: usize) -> String {
+        let mut rng = Random::default();
+        let chars = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        (0..len)
+            .map(|_| {
+                chars
+                    .chars()
+                    .nth(rng.random_range(0, chars.len() as u32)
+                        as usize)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    /// A function that generates a random salt for a password using the specified hash algorithm.
+    ///
+    /// The salt is drawn from the operating system's CSPRNG (`OsRng`).
+    ///
+    /// The formats differ between algorithms, but `new` uses a salt
+    /// string as its raw bytes and never decodes it, so a salt
+    /// generated for one algorithm is equally valid for another. The
+    /// stored salt is those same bytes, so `verify` hashes exactly what
+    /// `new` did. To hash the decoded bytes of a base64 salt instead,
+    /// use `new_with_salt_encoding`, or `generate_salt_bytes`.
+    pub fn generate_salt(algo: &str) -> Result<String, HshError> {
+        Self::generate_salt_with_rng(algo, &mut OsRng)
+    }
+
+    /// A function that generates `count` distinct salts for the
+    /// specified hash algorithm, for bulk provisioning.
+    ///
+    /// All salts are drawn from a single `OsRng` instance, in the
+    /// formats of `generate_salt_with_rng`. A salt equal to an earlier
+    /// one is drawn again, so the returned salts are all different.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let salts = Hash::generate_salts("scrypt", 3).unwrap();
+    /// assert_eq!(salts.len(), 3);
+    /// assert_ne!(salts[0], salts[1]);
+    /// ```
+    pub fn generate_salts(
+        algo: &str,
+        count: usize,
+    ) -> Result<Vec<String>, HshError> {
+        let mut rng = OsRng;
+        let mut seen = HashSet::with_capacity(count);
+        let mut salts = Vec::with_capacity(count);
+        while salts.len() < count {
+            let salt = Self::generate_salt_with_rng(algo, &mut rng)?;
+            if seen.insert(salt.clone()) {
+                salts.push(salt);
+            }
+        }
+        Ok(salts)
+    }
+
+    /// A function that generates a salt for the specified hash
+    /// algorithm, drawing its randomness from `rng`.
+    ///
+    /// `generate_salt` calls this with `OsRng`. Passing a seeded RNG
+    /// instead makes the salt reproducible, which is useful in tests.
+    ///
+    /// - Argon2i: 16 alphanumeric characters
+    /// - Bcrypt: 16 random bytes, base64 encoded
+    /// - Scrypt: 32 random bytes, base64 encoded
+    pub fn generate_salt_with_rng<R: RngCore>(
+        algo: &str,
+        rng: &mut R,
+    ) -> Result<String, HshError> {
+        match normalize_algorithm(algo).as_str() {
+            #[cfg(feature = "algo-argon2")]
+            "argon2i" | "argon2id" => {
+                const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+                // Bytes at or above the largest multiple of the
+                // alphabet size are redrawn, so that every character
+                // is equally likely.
+                let limit = 256 - 256 % CHARS.len();
+                let mut salt = String::with_capacity(16);
+                while salt.len() < 16 {
+                    let mut byte = [0u8; 1];
+                    rng.fill_bytes(&mut byte);
+                    let byte = usize::from(byte[0]);
+                    if byte < limit {
+                        salt.push(char::from(
+                            CHARS[byte % CHARS.len()],
+                        ));
+                    }
+                }
+                Ok(salt)
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            "bcrypt" => {
+                let mut salt = [0u8; 16];
+                rng.fill_bytes(&mut salt);
+                Ok(general_purpose::STANDARD.encode(salt))
+            }
+            #[cfg(feature = "algo-scrypt")]
+            "scrypt" => {
+                let mut salt = [0u8; 32];
+                rng.fill_bytes(&mut salt);
+                Ok(general_purpose::STANDARD.encode(salt))
+            }
+            _ => Err(HshError::UnsupportedAlgorithm(algo.to_string())),
+        }
+    }
+
+    /// A function that generates a random salt for the specified hash
+    /// algorithm as raw bytes, drawn from `OsRng`.
+    ///
+    /// Unlike `generate_salt`, the bytes are not encoded, whatever the
+    /// algorithm, so they can be stored in a binary column and passed
+    /// as they are to `new_bytes`:
+    ///
+    /// - Argon2i, Argon2id and Bcrypt: 16 bytes
+    /// - Scrypt: 32 bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, hash_algorithm::HashAlgorithm};
+    ///
+    /// let salt = Hash::generate_salt_bytes("scrypt").unwrap();
+    /// assert_eq!(salt.len(), 32);
+    ///
+    /// let hash = Hash::new_bytes(b"password123", salt, HashAlgorithm::Scrypt).unwrap();
+    /// assert_eq!(hash.verify("password123"), Ok(true));
+    /// ```
+    pub fn generate_salt_bytes(
+        algo: &str,
+    ) -> Result<Vec<u8>, HshError> {
+        let len = match normalize_algorithm(algo).as_str() {
+            #[cfg(feature = "algo-argon2")]
+            "argon2i" | "argon2id" => 16,
+            #[cfg(feature = "algo-bcrypt")]
+            "bcrypt" => 16,
+            #[cfg(feature = "algo-scrypt")]
+            "scrypt" => 32,
+            _ => {
+                return Err(HshError::UnsupportedAlgorithm(
+                    algo.to_string(),
+                ))
+            }
+        };
+        let mut salt = vec![0u8; len];
+        OsRng.fill_bytes(&mut salt);
+        Ok(salt)
+    }
+
+    /// A function that returns the hash value of a hash object.
+    pub fn hash(&self) -> &[u8] {
+        &self.hash
+    }
+
+    /// A function that returns the length of the hash value of a hash object.
+    pub fn hash_length(&self) -> usize {
+        self.hash.len()
+    }
+
+    /// A function that creates a new hash object from a password, salt, and hash algorithm.
+    ///
+    /// The algorithm name is parsed, then the hash is created with
+    /// `new_with_algorithm`. Passwords shorter than `MIN_PASSWORD_LEN`
+    /// bytes are rejected; see `new_with_policy` for other limits.
+    pub fn new(
+        password: &str,
+        salt: &str,
+        algo: &str,
+    ) -> Result<Self, HshError> {
+        Self::new_with_policy(
+            password,
+            salt,
+            algo,
+            crate::MIN_PASSWORD_LEN,
+        )
+    }
+
+    /// A function that creates a new hash object like `new`, checking
+    /// the password against the given policy instead of the default
+    /// minimum length.
+    ///
+    /// The policy may be a `PasswordPolicy` or just a minimum length
+    /// in bytes. Passwords outside of it are rejected with
+    /// `HshError::PasswordTooShort` or `HshError::PasswordTooLong`,
+    /// reporting the configured limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{
+    ///     error::HshError, hash::Hash, password_policy::PasswordPolicy,
+    /// };
+    ///
+    /// // Migrating a legacy short password
+    /// let hash = Hash::new_with_policy("abcd", "somesalt", "argon2i", 4).unwrap();
+    /// assert!(hash.verify("abcd").unwrap());
+    ///
+    /// let policy = PasswordPolicy::new(12).with_max_len(64);
+    /// assert_eq!(
+    ///     Hash::new_with_policy("password123", "somesalt", "argon2i", policy),
+    ///     Err(HshError::PasswordTooShort { min: 12 })
+    /// );
+    /// ```
+    pub fn new_with_policy(
+        password: &str,
+        salt: &str,
+        algo: &str,
+        policy: impl Into<PasswordPolicy>,
+    ) -> Result<Self, HshError> {
+        let algorithm =
+            HashAlgorithm::from_str(algo).map_err(|_| {
+                HshError::UnsupportedAlgorithm(algo.to_string())
+            })?;
+        Self::new_with_algorithm_and_policy(
+            password,
+            salt,
+            algorithm,
+            &policy.into(),
+        )
+    }
+
+    /// A function that creates a new hash object like `new`, taking
+    /// the algorithm as a `HashAlgorithm` rather than by name.
+    ///
+    /// The library defaults are used for the algorithm's parameters,
+    /// unless `set_global_defaults` was called with options for the
+    /// same algorithm, in which case their parameters are used.
+    ///
+    /// The salt is used as the raw bytes of the string, whatever it
+    /// looks like: a base64 salt is not decoded, so any encoding is
+    /// accepted with every algorithm. Only its length is checked
+    /// against `HashAlgorithm::min_salt_len`. Bcrypt ignores the salt.
+    ///
+    /// A warning is logged when the password looks like a PHC string
+    /// (`$argon2i$...`, `$2b$...`), which usually means an existing
+    /// hash is being hashed again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, hash_algorithm::HashAlgorithm};
+    ///
+    /// let hash =
+    ///     Hash::new_with_algorithm("password123", "somesalt", HashAlgorithm::Scrypt)
+    ///         .unwrap();
+    /// assert_eq!(hash, Hash::new("password123", "somesalt", "scrypt").unwrap());
+    /// ```
+    pub fn new_with_algorithm(
+        password: &str,
+        salt: &str,
+        algorithm: HashAlgorithm,
+    ) -> Result<Self, HshError> {
+        Self::new_with_algorithm_and_policy(
+            password,
+            salt,
+            algorithm,
+            &PasswordPolicy::default(),
+        )
+    }
+
+    /// Creates a new `Hash` like `new_with_algorithm`, checking the
+    /// password against `policy`.
+    fn new_with_algorithm_and_policy(
+        password: &str,
+        salt: &str,
+        algorithm: HashAlgorithm,
+        policy: &PasswordPolicy,
+    ) -> Result<Self, HshError> {
+        check_minimums(
+            algorithm,
+            policy,
+            password.as_bytes(),
+            salt.as_bytes(),
+        )?;
+
+        // Hashing a stored hash again is a common mistake. Only a
+        // warning is logged, as a legitimate password may look alike;
+        // the password itself is never logged.
+        if looks_like_phc(password) {
+            log::warn!(
+                "The password looks like a PHC string; it may be a hash \
+                 that is being hashed again"
+            );
+        }
+
+        // Parameters set with `set_global_defaults` take the place of
+        // the library defaults for their algorithm.
+        if let Some(options) = global_defaults()
+            .filter(|options| options.algorithm == algorithm)
+        {
+            return Self::new_with_params(
+                password.as_bytes(),
+                salt.as_bytes().to_vec(),
+                algorithm,
+                options.params,
+                policy,
+            );
+        }
+
+        let hash = match algorithm {
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i => {
+                Argon2i::hash_password(password, salt)
+                    .map_err(HshError::Backend)?
+            }
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2id => {
+                Argon2id::hash_password(password, salt)
+                    .map_err(HshError::Backend)?
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => {
+                Bcrypt::hash_password(password, salt)
+                    .map_err(HshError::Backend)?
+            }
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => {
+                Scrypt::hash_password(password, salt)
+                    .map_err(HshError::Backend)?
+            }
+        };
+
+        #[cfg(all(feature = "warn-weak", feature = "algo-bcrypt"))]
+        if algorithm == HashAlgorithm::Bcrypt {
+            warn_bcrypt_limitations();
+        }
+
+        // Bcrypt ignores the given salt and embeds its own, which is
+        // decoded from the hash as in `new_bcrypt`.
+        let salt = match algorithm {
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => std::str::from_utf8(&hash)
+                .ok()
+                .and_then(|mcf| mcf.rsplit('$').next())
+                .ok_or(HshError::InvalidHashString)
+                .and_then(decode_bcrypt_salt)?,
+            #[allow(unreachable_patterns)]
+            _ => salt.as_bytes().to_vec(),
+        };
+
+        metrics::record_hash();
+        Ok(Self {
+            hash,
+            salt,
+            algorithm,
+            params: Some(Params::default_for(algorithm)),
+            original_phc: None,
+            salt_source: provided_salt_source(algorithm),
+        })
+    }
+
+    /// A function that creates a new hash object like `new`, generating
+    /// the salt with `generate_salt` when none is given.
+    ///
+    /// The salt's origin is recorded, see `salt_source`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{
+    ///     hash::Hash,
+    ///     salt_source::{SaltRng, SaltSource},
+    /// };
+    ///
+    /// let hash = Hash::create("password123", None, "argon2i").unwrap();
+    /// assert_eq!(hash.salt_source(), SaltSource::Generated(SaltRng::OsRng));
+    /// assert!(hash.verify("password123").unwrap());
+    /// ```
+    pub fn create(
+        password: &str,
+        salt: Option<&str>,
+        algo: &str,
+    ) -> Result<Self, HshError> {
+        let Some(salt) = salt else {
+            let salt = Self::generate_salt(algo)?;
+            return Ok(
+                Self::new(password, &salt, algo)?.with_generated_salt()
+            );
+        };
+        Self::new(password, salt, algo)
+    }
+
+    /// Records that the salt of a hash object came from
+    /// `generate_salt`. Bcrypt reports its own generator instead, and
+    /// is left as is.
+    fn with_generated_salt(mut self) -> Self {
+        if self.salt_source == SaltSource::Provided {
+            self.salt_source = SaltSource::Generated(SaltRng::OsRng);
+        }
+        self
+    }
+
+    /// A function that creates a new hash object like `new`, after
+    /// normalizing the password to Unicode NFKC.
+    ///
+    /// The same characters can be typed as different code points, for
+    /// example `é` as one precomposed character (NFC) or as `e` and a
+    /// combining accent (NFD), depending on the keyboard and operating
+    /// system. Normalizing, as recommended by PRECIS (RFC 8265) and
+    /// OWASP, makes them hash alike.
+    ///
+    /// Hashes made with this function must be checked with
+    /// `verify_normalized`, and hashes made without it must not be:
+    /// normalization has to match between hashing and verification.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_normalized("caf\u{e9}-password", "somesalt", "argon2i").unwrap();
+    /// assert_eq!(hash.verify_normalized("cafe\u{301}-password"), Ok(true));
+    /// ```
+    #[cfg(feature = "unicode")]
+    pub fn new_normalized(
+        password: &str,
+        salt: &str,
+        algo: &str,
+    ) -> Result<Self, HshError> {
+        Self::new(&password.nfkc().collect::<String>(), salt, algo)
+    }
+
+    /// A function that creates a new hash object like `new`, on the
+    /// Tokio blocking thread pool.
+    ///
+    /// It must be called from within a Tokio runtime.
+    #[cfg(feature = "async")]
+    pub async fn new_async(
+        password: String,
+        salt: String,
+        algo: String,
+    ) -> Result<Self, HshError> {
+        tokio::task::spawn_blocking(move || {
+            Self::new(&password, &salt, &algo)
+        })
+        .await
+        .map_err(|e| HshError::Backend(e.to_string()))?
+    }
+
+    /// A function that parses a JSON string into a hash object.
+    pub fn parse(
+        input: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let hash: Hash = serde_json::from_str(input)?;
+        Ok(hash)
+    }
+
+    /// A function that parses a hash string into a hash algorithm.
+    pub fn parse_algorithm(
+        hash_str: &str,
+    ) -> Result<HashAlgorithm, HshError> {
+        let parts: Vec<&str> = hash_str.split('$').collect();
+        if parts.len() < 2 {
+            return Err(HshError::InvalidHashString);
+        }
+        match normalize_algorithm(parts[1]).as_str() {
+            #[cfg(feature = "algo-argon2")]
+            "argon2i" => Ok(HashAlgorithm::Argon2i),
+            #[cfg(feature = "algo-argon2")]
+            "argon2id" => Ok(HashAlgorithm::Argon2id),
+            #[cfg(feature = "algo-bcrypt")]
+            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
+            #[cfg(feature = "algo-scrypt")]
+            "scrypt" => Ok(HashAlgorithm::Scrypt),
+            _ => Err(HshError::UnsupportedAlgorithm(
+                parts[1].to_string(),
+            )),
+        }
+    }
+
+    /// A function that returns the salt used to hash a password.
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    /// A function that returns the salt used to hash a password, base64
+    /// encoded.
+    ///
+    /// Bcrypt salts use the Bcrypt alphabet, as in the 22 characters
+    /// that follow the cost in a Bcrypt hash. Other salts use the
+    /// standard alphabet without padding, as in PHC strings.
+    pub fn salt_encoded(&self) -> String {
+        match self.algorithm {
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => BCRYPT_B64.encode(&self.salt),
+            #[allow(unreachable_patterns)]
+            _ => PHC_B64.encode(&self.salt),
+        }
+    }
+
+    /// A function that sets the hash value of a hash object.
+    ///
+    /// The new value is checked against the hash's algorithm: Argon2
+    /// and Scrypt digests must have a length the algorithm can produce,
+    /// and Bcrypt hashes must be a whole `$2b$...` string. Other values
+    /// are rejected with `HshError::InvalidParams` or
+    /// `HshError::InvalidHashString`, leaving the hash unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let mut hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    ///
+    /// assert!(hash.set_hash(&[1, 2, 3, 4, 5]).is_err());
+    /// assert!(hash.set_hash(&[0; 32]).is_ok());
+    /// ```
+    pub fn set_hash(&mut self, hash: &[u8]) -> Result<(), HshError> {
+        check_digest(self.algorithm, hash)?;
+        self.hash = hash.to_vec();
+        self.original_phc = None;
+        Ok(())
+    }
+
+    /// A function that sets the password of a hash object.
+    ///
+    /// The hash is rebuilt from `password`, `salt` and `algo`, so the
+    /// salt, algorithm, parameters and digest always belong together.
+    /// When `algo` names the hash's own algorithm, its parameters and
+    /// digest length are kept, legacy parameters included; otherwise
+    /// the defaults of `algo` are used. Bcrypt generates its own salt
+    /// and ignores `salt`, as in `new`. On error, the hash is left
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let mut hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// hash.set_password("password456", "othersalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.salt(), b"othersalt");
+    /// assert_eq!(hash.verify("password456"), Ok(true));
+    /// ```
+    pub fn set_password(
+        &mut self,
+        password: &str,
+        salt: &str,
+        algo: &str,
+    ) -> Result<(), HshError> {
+        let algorithm =
+            HashAlgorithm::from_str(algo).map_err(|_| {
+                HshError::UnsupportedAlgorithm(algo.to_string())
+            })?;
+        let (params, output_len) = if algorithm == self.algorithm {
+            (self.parameters(), Some(self.hash.len()))
+        } else {
+            (Params::default_for(algorithm), None)
+        };
+        *self = Self::new_with_params_sized(
+            password.as_bytes(),
+            salt.as_bytes().to_vec(),
+            algorithm,
+            params,
+            &PasswordPolicy::default(),
+            output_len,
+        )?;
+        Ok(())
+    }
+
+    /// A function that sets the salt of a hash object, rehashing the
+    /// password with it.
+    ///
+    /// The digest depends on the salt, so the two are replaced
+    /// together, with the hash's algorithm, parameters and digest
+    /// length; a changed salt never leaves a digest it does not belong
+    /// to. `password` must verify against the current digest, otherwise
+    /// `HshError::PasswordMismatch` is returned, so the stored
+    /// credential cannot be replaced without knowing it. Bcrypt
+    /// generates its own salt and ignores `salt`, as in `new`. On
+    /// error, the hash is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let mut hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// hash.set_salt("password123", b"othersalt").unwrap();
+    ///
+    /// assert_eq!(hash.salt(), b"othersalt");
+    /// assert_eq!(hash.verify("password123"), Ok(true));
+    /// ```
+    pub fn set_salt(
+        &mut self,
+        password: &str,
+        salt: &[u8],
+    ) -> Result<(), HshError> {
+        if !self.verify(password)? {
+            return Err(HshError::PasswordMismatch);
+        }
+        *self = Self::new_with_params_sized(
+            password.as_bytes(),
+            salt.to_vec(),
+            self.algorithm,
+            self.parameters(),
+            &PasswordPolicy::default(),
+            Some(self.hash.len()),
+        )?;
+        Ok(())
+    }
+
+    /// A function that encodes a hash object as a PHC string.
+    ///
+    /// Argon2 and Scrypt hashes are written with their parameters,
+    /// salt and digest, in the layout read by `from_string`. Bcrypt
+    /// hashes are already stored in their modular crypt form
+    /// (`$2b$...`), which is returned as is.
+    ///
+    /// Encoding only fails when the stored state is inconsistent: a
+    /// Bcrypt hash that is not valid UTF-8, or parameters that do not
+    /// belong to the algorithm. Hashes built by this library always
+    /// encode; `display_phc` offers the same encoding without a
+    /// `Result` to unwrap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// let phc = hash.to_phc_string().unwrap();
+    ///
+    /// assert!(phc.starts_with("$argon2i$v=19$m=4096,t=3,p=1$"));
+    /// assert_eq!(Hash::from_string(&phc).unwrap(), hash);
+    /// ```
+    pub fn to_phc_string(&self) -> Result<String, HshError> {
+        phc::encode(self)
+    }
+
+    /// A function that encodes a hash object in the compact binary
+    /// layout described in the `binary` module.
+    ///
+    /// The output starts with a format version byte and is much
+    /// smaller than the JSON or PHC forms. A salt or digest too long
+    /// for the format is reported as an `HshError::InvalidParams`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// let bytes = hash.to_bytes().unwrap();
+    ///
+    /// assert!(bytes.len() < hash.to_phc_string().unwrap().len());
+    /// assert_eq!(Hash::from_bytes(&bytes).unwrap(), hash);
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, HshError> {
+        binary::encode(self)
+    }
+
+    /// A function that decodes a hash object written by `to_bytes`.
+    ///
+    /// Truncated or otherwise malformed input is reported as an
+    /// `HshError::CorruptStoredHash`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HshError> {
+        binary::decode(bytes)
+    }
+
+    /// A function that encodes a hash object with bincode, through its
+    /// serde implementation.
+    ///
+    /// The fields are those of the JSON form, including the
+    /// serialization version, so `from_bincode` applies the same checks
+    /// and migrations as JSON deserialization. The algorithm is written
+    /// as its fixed discriminant. `Params` variants are numbered in
+    /// declaration order among the enabled `algo-*` features, so the
+    /// output is meant to be read by a build with the same algorithms;
+    /// `to_bytes` has no such restriction. Encoding failures are
+    /// reported as an `HshError::Io`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// let bytes = hash.to_bincode().unwrap();
+    ///
+    /// assert!(bytes.len() < serde_json::to_vec(&hash).unwrap().len());
+    /// assert_eq!(Hash::from_bincode(&bytes).unwrap(), hash);
+    /// ```
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, HshError> {
+        let fields =
+            BincodeFields::from(HashFields::from(self.clone()));
+        bincode::serialize(&fields)
+            .map_err(|e| HshError::Io(e.to_string()))
+    }
+
+    /// A function that decodes a hash object written by `to_bincode`.
+    ///
+    /// Malformed input is reported as an `HshError::CorruptStoredHash`,
+    /// as are unknown serialization versions.
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, HshError> {
+        let fields: BincodeFields = bincode::deserialize(bytes)
+            .map_err(|e| {
+                HshError::CorruptStoredHash(format!(
+                    "invalid bincode: {}",
+                    e
+                ))
+            })?;
+        Hash::try_from(HashFields::from(fields))
+    }
+
+    /// A function that returns a human-readable summary of a hash
+    /// object, for auditing stored hashes.
+    ///
+    /// The summary lists the algorithm, its parameters (from
+    /// `parameters`) and the salt and digest lengths in bytes; it never
+    /// includes the salt or digest themselves. Argon2 memory costs are
+    /// shown in MiB when they are whole MiB, in KiB otherwise. For
+    /// Bcrypt, the digest length is that of the stored modular crypt
+    /// string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.describe(), "scrypt ln=14 r=8 p=1 salt=8B digest=64B");
+    /// ```
+    pub fn describe(&self) -> String {
+        let params = match self.parameters() {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                let memory = if m_cost % 1024 == 0 {
+                    format!("{}MiB", m_cost / 1024)
+                } else {
+                    format!("{}KiB", m_cost)
+                };
+                format!(
+                    "v={} m={} t={} p={}",
+                    version, memory, t_cost, p_cost
+                )
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } => format!("cost={}", cost),
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { log_n, r, p } => {
+                format!("ln={} r={} p={}", log_n, r, p)
+            }
+        };
+        format!(
+            "{} {} salt={}B digest={}B",
+            self.algorithm.to_string().to_lowercase(),
+            params,
+            self.salt.len(),
+            self.hash.len()
+        )
+    }
+
+    /// A function that returns a short, non-reversible identifier of a
+    /// hash object, safe to write to logs.
+    ///
+    /// The fingerprint is the first 8 hex characters of the SHA-256 of
+    /// the PHC string, or of the `to_bytes` encoding (the raw digest
+    /// if that fails too) for hashes that have no valid PHC form.
+    /// Identical hashes share a fingerprint, so log lines can be
+    /// correlated without exposing the digest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// let fingerprint = hash.fingerprint();
+    ///
+    /// assert_eq!(fingerprint.len(), 8);
+    /// assert_eq!(fingerprint, hash.clone().fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        let digest = match self.to_phc_string() {
+            Ok(phc) => Sha256::digest(phc.as_bytes()),
+            Err(_) => Sha256::digest(
+                self.to_bytes().unwrap_or_else(|_| self.hash.clone()),
+            ),
+        };
+        encode_hex(&digest[..4])
+    }
+
+    /// A function that returns a value displaying the PHC string of a
+    /// hash object, for use with `format!`, `println!` or `to_string`.
+    ///
+    /// Formatting returns `fmt::Error` in the cases where
+    /// `to_phc_string` fails, which cannot happen for hashes built by
+    /// this library.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// let phc = hash.display_phc().to_string();
+    ///
+    /// assert!(phc.starts_with("$argon2i$v=19$m=4096,t=3,p=1$"));
+    /// ```
+    pub fn display_phc(&self) -> PhcDisplay<'_> {
+        PhcDisplay { hash: self }
+    }
+
+    /// A function that writes the PHC string of a hash object to a
+    /// writer, without building an intermediate `String`.
+    ///
+    /// The output is identical to `to_phc_string`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let mut buf = Vec::new();
+    /// hash.write_phc(&mut buf).unwrap();
+    ///
+    /// assert_eq!(buf, hash.to_phc_string().unwrap().into_bytes());
+    /// ```
+    pub fn write_phc<W: Write>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), HshError> {
+        phc::write(self, w)
+    }
+
+    /// A function that checks whether two PHC strings describe the
+    /// same hash.
+    ///
+    /// Both strings are parsed and their canonical forms (algorithm,
+    /// parameters, salt and digest) are compared, so differences in
+    /// base64 padding or parameter ordering are ignored. The digests
+    /// are compared in constant time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let a = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+    /// let b = "$argon2i$v=16$p=1,t=2,m=256$c29tZXNhbHQ=$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg=";
+    ///
+    /// assert!(Hash::phc_equivalent(a, b).unwrap());
+    /// ```
+    pub fn phc_equivalent(a: &str, b: &str) -> Result<bool, HshError> {
+        let a = Self::parse_phc(a)?;
+        let b = Self::parse_phc(b)?;
+
+        let same_digest: bool = a.hash.ct_eq(&b.hash).into();
+        Ok(a.algorithm == b.algorithm
+            && a.stored_params()? == b.stored_params()?
+            && a.salt == b.salt
+            && same_digest)
+    }
+
+    /// A function that compares two hash objects without the timing
+    /// leak of `PartialEq`.
+    ///
+    /// The algorithms are compared first, as they are not secret. The
+    /// digests and salts are then both compared in constant time, so
+    /// the time taken does not reveal where they first differ. Only
+    /// their lengths may leak. `PartialEq` remains fine where nothing
+    /// is secret, as in test assertions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let same = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let other = Hash::new("password456", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert!(hash.ct_eq(&same));
+    /// assert!(!hash.ct_eq(&other));
+    /// ```
+    pub fn ct_eq(&self, other: &Hash) -> bool {
+        if self.algorithm != other.algorithm {
+            return false;
+        }
+        (self.hash.ct_eq(&other.hash) & self.salt.ct_eq(&other.salt))
+            .into()
+    }
+
+    /// A function that converts a hash object to a string representation.
+    pub fn to_string_representation(&self) -> String {
+        let hash_str = self
+            .hash
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<String>>()
+            .join("");
+
+        format!("{}:{}", String::from_utf8_lossy(&self.salt), hash_str)
+    }
+
+    /// A function that converts a hash object to the same string
+    /// representation as `to_string_representation`, hex encoding the
+    /// digest without data-dependent branches.
+    ///
+    /// Prefer it where the output is compared or otherwise handled as
+    /// a secret. See `hex::encode_ct`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// assert_eq!(
+    ///     hash.to_string_representation_ct(),
+    ///     hash.to_string_representation()
+    /// );
+    /// ```
+    pub fn to_string_representation_ct(&self) -> String {
+        format!(
+            "{}:{}",
+            String::from_utf8_lossy(&self.salt),
+            hex::encode_ct(&self.hash)
+        )
+    }
+
+    /// A function that verifies a password against a hash object.
+    ///
+    /// The password is hashed again with the stored salt and
+    /// parameters, and the result is compared with the stored hash in
+    /// constant time. Nothing is printed or logged.
+    ///
+    /// Returns `Ok(false)` when the password does not match, and
+    /// `Err(HshError::CorruptStoredHash)` when the stored hash is
+    /// structurally broken and could never match any password.
+    /// The stored hash is checked before the password is hashed, so a
+    /// broken hash, or a Bcrypt hash holding another algorithm's
+    /// string (`Err(HshError::AlgorithmMismatch)`), fails without
+    /// running the algorithm.
+    ///
+    /// The password may be borrowed (`&str`) or owned (`String`);
+    /// neither is copied.
+    // Anonymous lifetimes are not allowed in `impl Trait` arguments.
+    #[allow(single_use_lifetimes)]
+    pub fn verify<'a>(
+        &self,
+        password: impl Into<Cow<'a, str>>,
+    ) -> Result<bool, HshError> {
+        self.verify_bytes(password.into().as_bytes())
+    }
+
+    /// A function that verifies a password given as bytes against a
+    /// hash object.
+    ///
+    /// This behaves like `verify`. Bcrypt only accepts UTF-8
+    /// passwords, so for Bcrypt hashes other passwords are rejected
+    /// with `HshError::NonUtf8Password`.
+    pub fn verify_bytes(
+        &self,
+        password: &[u8],
+    ) -> Result<bool, HshError> {
+        let result = self.stored_params().and_then(|params| match self
+            .algorithm
+            .verify_method()
+        {
+            VerifyMethod::Recompute => {
+                let recomputed = self.recompute(password, params)?;
+                Ok(recomputed.ct_eq(&self.hash).into())
+            }
+            VerifyMethod::Backend => {
+                self.verify_in_backend(password, params)
+            }
+        });
+        metrics::record_verify(&result);
+        result
+    }
+
+    /// A function that verifies a password like `verify`, and returns
+    /// the parameters it verified with.
+    ///
+    /// These are the recorded parameters (or `Params::legacy` when
+    /// none were recorded), and for Bcrypt the cost of the stored
+    /// string, as reported by `parameters`. Comparing them with the
+    /// parameters a hash was expected to have helps diagnose a password
+    /// that fails to verify.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, params::Params};
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    ///
+    /// assert_eq!(
+    ///     hash.verify_with_params_out("password123"),
+    ///     Ok((true, Params::Bcrypt { cost: 4 }))
+    /// );
+    /// ```
+    pub fn verify_with_params_out(
+        &self,
+        password: &str,
+    ) -> Result<(bool, Params), HshError> {
+        let verified = self.verify(password)?;
+        Ok((verified, self.parameters()))
+    }
+
+    /// A function that verifies a password like `verify`, and reports
+    /// in the same result whether the stored hash should be migrated.
+    ///
+    /// `deprecated_algorithm` is derived from `security_level`, which
+    /// also flags Bcrypt below `MIN_BCRYPT_COST`, and `urgent_rehash` from
+    /// `security_assessment`. Both are reported whether or not the
+    /// password matches, but a hash should only be replaced after a
+    /// successful login.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    /// let outcome = hash.verify_and_check("password123").unwrap();
+    ///
+    /// assert!(outcome.verified);
+    /// assert!(outcome.urgent_rehash);
+    /// assert!(outcome.deprecated_algorithm);
+    /// ```
+    pub fn verify_and_check(
+        &self,
+        password: &str,
+    ) -> Result<VerifyOutcome, HshError> {
+        Ok(VerifyOutcome {
+            verified: self.verify(password)?,
+            urgent_rehash: self.security_assessment().urgent_rehash,
+            deprecated_algorithm: self.security_level()
+                == SecurityLevel::Deprecated,
+        })
+    }
+
+    /// A function that verifies a password against the stored digest
+    /// with each of `candidate_salts`, returning whether any matched.
+    ///
+    /// This is for legacy schemes where the salt is only known up to a
+    /// lookup. Every candidate is hashed and compared in constant time,
+    /// without stopping at a match, so the timing does not reveal which
+    /// salt matched. The cost is therefore one full key derivation per
+    /// candidate. If any candidate cannot be hashed, the first error is
+    /// returned after all of them were tried.
+    ///
+    /// Bcrypt embeds its salt in the hash, so Bcrypt hashes are
+    /// rejected with `HshError::InvalidParams`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let candidates: [&[u8]; 2] = [b"othersalt", b"somesalt"];
+    ///
+    /// assert_eq!(hash.verify_salts_ct("password123", &candidates), Ok(true));
+    /// ```
+    pub fn verify_salts_ct(
+        &self,
+        password: &str,
+        candidate_salts: &[&[u8]],
+    ) -> Result<bool, HshError> {
+        let params = self.stored_params()?;
+        if self.algorithm.verify_method() != VerifyMethod::Recompute {
+            return Err(HshError::InvalidParams(format!(
+                "{} embeds its salt and cannot verify candidate salts",
+                self.algorithm
+            )));
+        }
+
+        let mut matched = Choice::from(0);
+        let mut error = None;
+        for salt in candidate_salts {
+            match self.recompute_with_salt(
+                password.as_bytes(),
+                salt,
+                params,
+            ) {
+                Ok(recomputed) => {
+                    matched |= recomputed.ct_eq(&self.hash)
+                }
+                Err(e) => {
+                    let _ = error.get_or_insert(e);
+                }
+            }
+        }
+        match error {
+            Some(e) => Err(e),
+            None => Ok(matched.into()),
+        }
+    }
+
+    /// A function that verifies a password like `verify`, after
+    /// checking that the hash uses the `expected` algorithm.
+    ///
+    /// `verify` trusts the stored algorithm, which is authoritative for
+    /// hashes from a trusted source. Where the stored algorithm could
+    /// have been tampered with, this prevents a strong hash from being
+    /// swapped for one checked on a weaker path: a hash of another
+    /// algorithm fails with `HshError::AlgorithmMismatch`, before the
+    /// password is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{error::HshError, hash::Hash, hash_algorithm::HashAlgorithm};
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.verify_expecting("password123", HashAlgorithm::Scrypt), Ok(true));
+    /// assert!(matches!(
+    ///     hash.verify_expecting("password123", HashAlgorithm::Argon2i),
+    ///     Err(HshError::AlgorithmMismatch { .. })
+    /// ));
+    /// ```
+    pub fn verify_expecting(
+        &self,
+        password: &str,
+        expected: HashAlgorithm,
+    ) -> Result<bool, HshError> {
+        if self.algorithm != expected {
+            return Err(HshError::AlgorithmMismatch {
+                expected,
+                found: self.algorithm,
+            });
+        }
+        self.verify(password)
+    }
+
+    /// A function that verifies a password against a stored PHC string,
+    /// provided its parameters meet the minimum `min`.
+    ///
+    /// Where the stored string could have been tampered with, an
+    /// attacker may replace it with a cheap, weak-parameter hash of a
+    /// password they know. This rejects such strings with
+    /// `HshError::ParamsBelowPolicy` before the password is used; see
+    /// `Params::check_at_least` for how parameters are compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{error::HshError, hash::Hash, params::Params};
+    ///
+    /// let stored = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+    /// let policy = Params::Argon2 { version: 0x13, m_cost: 4096, t_cost: 3, p_cost: 1 };
+    ///
+    /// assert!(matches!(
+    ///     Hash::verify_with_min_params(stored, "password123", &policy),
+    ///     Err(HshError::ParamsBelowPolicy(_))
+    /// ));
+    /// ```
+    pub fn verify_with_min_params(
+        stored: &str,
+        password: &str,
+        min: &Params,
+    ) -> Result<bool, HshError> {
+        let hash = Self::parse_phc(stored)?;
+        hash.stored_params()?.check_at_least(min)?;
+        hash.verify(password)
+    }
+
+    /// A function that verifies a password like `verify` and, when it
+    /// matches a hash made with other options than `target`, rehashes
+    /// it with `target`.
+    ///
+    /// This is the complete login-and-migrate step for parameter
+    /// rollouts: the returned hash, if any, is ready to be persisted in
+    /// place of this one. It uses a fresh salt from `generate_salt`.
+    /// No hash is returned when the password does not match, or when
+    /// `scheme` already equals `target`.
+    ///
+    /// When `target` keeps the algorithm, the migrated hash keeps the
+    /// digest length of this one, so only the parameters change. A
+    /// hash migrated to another algorithm gets that algorithm's
+    /// default digest length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{
+    ///     hash::Hash,
+    ///     hash_algorithm::HashAlgorithm,
+    ///     hash_options::HashOptions,
+    ///     params::Params,
+    /// };
+    ///
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    /// let target =
+    ///     HashOptions::new(HashAlgorithm::Bcrypt, Params::Bcrypt { cost: 5 })
+    ///         .unwrap();
+    ///
+    /// let (verified, migrated) = hash.verify_and_migrate("password123", &target).unwrap();
+    /// assert!(verified);
+    /// assert_eq!(migrated.unwrap().scheme(), target);
+    /// ```
+    pub fn verify_and_migrate(
+        &self,
+        password: &str,
+        target: &HashOptions,
+    ) -> Result<(bool, Option<Hash>), HshError> {
+        if !self.verify(password)? {
+            return Ok((false, None));
+        }
+        if self.scheme() == *target {
+            return Ok((true, None));
+        }
+
+        let algo = target.algorithm.to_string().to_lowercase();
+        let salt = Self::generate_salt(&algo)?;
+        let output_len = (target.algorithm == self.algorithm)
+            .then_some(self.hash.len());
+        let migrated = Self::new_with_params_sized(
+            password.as_bytes(),
+            salt.into_bytes(),
+            target.algorithm,
+            target.params,
+            &PasswordPolicy::default(),
+            output_len,
+        )?;
+        Ok((true, Some(migrated.with_generated_salt())))
+    }
+
+    /// A function that verifies a password like `verify`, and when it
+    /// matches, returns the same password hashed again with a fresh
+    /// salt.
+    ///
+    /// This is for policies that rotate salts at each successful
+    /// login. The returned hash keeps the algorithm, parameters and
+    /// digest length of this one, and uses a salt from
+    /// `generate_salt`. No hash is returned when the password does not
+    /// match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let rotated = hash.rotate_salt_on_verify("password123").unwrap().unwrap();
+    ///
+    /// assert_ne!(rotated.salt(), hash.salt());
+    /// assert_eq!(rotated.verify("password123"), Ok(true));
+    /// assert_eq!(hash.rotate_salt_on_verify("password456"), Ok(None));
+    /// ```
+    pub fn rotate_salt_on_verify(
+        &self,
+        password: &str,
+    ) -> Result<Option<Hash>, HshError> {
+        if !self.verify(password)? {
+            return Ok(None);
+        }
+
+        let algo = self.algorithm.to_string().to_lowercase();
+        let salt = Self::generate_salt(&algo)?;
+        let rotated = Self::new_with_params_sized(
+            password.as_bytes(),
+            salt.into_bytes(),
+            self.algorithm,
+            self.parameters(),
+            &PasswordPolicy::default(),
+            Some(self.hash.len()),
+        )?;
+        Ok(Some(rotated.with_generated_salt()))
+    }
+
+    /// A function that verifies many candidate passwords, each against
+    /// its own stored hash, returning the results in the same order.
+    ///
+    /// With the `rayon` feature the pairs are verified in parallel on
+    /// the Rayon thread pool; otherwise one after the other.
+    ///
+    /// This is meant for offline analysis, such as detecting
+    /// credential stuffing in bulk. It is not a login endpoint: it
+    /// applies none of the checks of `login_verify` and can occupy
+    /// every core for as long as the batch takes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    /// let pairs = vec![
+    ///     (hash.clone(), String::from("password123")),
+    ///     (hash, String::from("letmein")),
+    /// ];
+    ///
+    /// assert_eq!(Hash::verify_pairs(&pairs), vec![Ok(true), Ok(false)]);
+    /// ```
+    pub fn verify_pairs(
+        pairs: &[(Hash, String)],
+    ) -> Vec<Result<bool, HshError>> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            pairs
+                .par_iter()
+                .map(|(hash, password)| hash.verify(password))
+                .collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            pairs
+                .iter()
+                .map(|(hash, password)| hash.verify(password))
+                .collect()
+        }
+    }
+
+    /// A function that verifies several candidate passwords against
+    /// this hash, returning a result per candidate in the same order.
+    ///
+    /// With the `rayon` feature the candidates are verified in parallel
+    /// on the Rayon thread pool; otherwise one after the other.
+    ///
+    /// This is meant for authorized security testing only, such as
+    /// measuring how a stored hash holds up against a dictionary. Like
+    /// `verify_pairs`, it applies none of the checks of `login_verify`
+    /// and can occupy every core for as long as the batch takes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+    ///
+    /// assert_eq!(
+    ///     hash.verify_candidates(&["letmein", "password123"]),
+    ///     vec![Ok(false), Ok(true)]
+    /// );
+    /// ```
+    pub fn verify_candidates(
+        &self,
+        candidates: &[&str],
+    ) -> Vec<Result<bool, HshError>> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            candidates
+                .par_iter()
+                .map(|candidate| self.verify(*candidate))
+                .collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            candidates
+                .iter()
+                .map(|candidate| self.verify(*candidate))
+                .collect()
+        }
+    }
+
+    /// A function that verifies several passwords against this hash,
+    /// returning a result per password in the same order.
+    ///
+    /// Unlike `verify_candidates`, the passwords are verified one after
+    /// the other, and the work shared by every password is done once:
+    /// the stored hash is checked and the Argon2 memory or Scrypt
+    /// parameters are set up a single time. Bcrypt hashes are simply
+    /// verified in a loop, as their cost lies in the hash itself.
+    ///
+    /// This is meant for authorized security testing only, such as a
+    /// password-strength audit; see `verify_candidates`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(
+    ///     hash.verify_many(&["letmein", "password123"]),
+    ///     vec![Ok(false), Ok(true)]
+    /// );
+    /// ```
+    pub fn verify_many(
+        &self,
+        passwords: &[&str],
+    ) -> Vec<Result<bool, HshError>> {
+        let params = match self.stored_params().and_then(|params| {
+            self.check_stored_format()?;
+            Ok(params)
+        }) {
+            Ok(params) => params,
+            Err(e) => {
+                return passwords
+                    .iter()
+                    .map(|_| {
+                        let result = Err(e.clone());
+                        metrics::record_verify(&result);
+                        result
+                    })
+                    .collect();
+            }
+        };
+
+        match params {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                let variant = match self.algorithm {
+                    HashAlgorithm::Argon2id => {
+                        argon2::Algorithm::Argon2id
+                    }
+                    _ => argon2::Algorithm::Argon2i,
+                };
+                let mut hasher = ReusableArgon2i::with_algorithm(
+                    variant,
+                    version,
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                    self.hash.len(),
+                );
+                self.verify_each(passwords, |password| {
+                    hasher
+                        .as_mut()
+                        .map_err(|e| e.clone())?
+                        .hash(password, &self.salt)
+                })
+            }
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { log_n, r, p } => {
+                let hasher =
+                    ReusableScrypt::new(log_n, r, p, self.hash.len());
+                self.verify_each(passwords, |password| {
+                    hasher
+                        .as_ref()
+                        .map_err(|e| e.clone())?
+                        .hash(password, &self.salt)
+                })
+            }
+            #[allow(unreachable_patterns)]
+            _ => passwords
+                .iter()
+                .map(|password| self.verify(*password))
+                .collect(),
+        }
+    }
+
+    /// Compares the digest of each password, as derived by `digest`,
+    /// with the stored hash in constant time. Used by `verify_many`.
+    #[cfg(any(feature = "algo-argon2", feature = "algo-scrypt"))]
+    fn verify_each(
+        &self,
+        passwords: &[&str],
+        mut digest: impl FnMut(&[u8]) -> Result<Vec<u8>, HshError>,
+    ) -> Vec<Result<bool, HshError>> {
+        passwords
+            .iter()
+            .map(|password| {
+                let result =
+                    digest(password.as_bytes()).map(|output| {
+                        Zeroizing::new(output).ct_eq(&self.hash).into()
+                    });
+                metrics::record_verify(&result);
+                result
+            })
+            .collect()
+    }
+
+    /// A function that verifies a password against a hash stored as
+    /// separate components rather than as a PHC string.
+    ///
+    /// `digest`, `salt` and `params` are used exactly as stored; for
+    /// Bcrypt, `digest` is the whole `$2b$...` string and `salt` is
+    /// ignored. Returns `Err(HshError::InvalidParams)` if `params` do
+    /// not belong to `algo`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, hash_algorithm::HashAlgorithm, params::Params};
+    ///
+    /// let stored = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let params = Params::default_for(HashAlgorithm::Scrypt);
+    ///
+    /// let verified = Hash::verify_components(
+    ///     stored.hash(),
+    ///     stored.salt(),
+    ///     HashAlgorithm::Scrypt,
+    ///     &params,
+    ///     "password123",
+    /// );
+    /// assert_eq!(verified, Ok(true));
+    /// ```
+    pub fn verify_components(
+        digest: &[u8],
+        salt: &[u8],
+        algo: HashAlgorithm,
+        params: &Params,
+        password: &str,
+    ) -> Result<bool, HshError> {
+        Self::reconstruct(digest, salt, algo, *params)?.verify(password)
+    }
+
+    /// A function that verifies a password against a credential stored
+    /// as separate columns.
+    ///
+    /// This is `verify_components` for a `StoredCredential`, and the
+    /// columnar counterpart to verifying a PHC string with
+    /// `from_string` and `verify`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, stored_credential::StoredCredential};
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let json = serde_json::to_string(&StoredCredential::from(&hash)).unwrap();
+    ///
+    /// let credential: StoredCredential = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(Hash::verify_stored(&credential, "password123"), Ok(true));
+    /// assert_eq!(Hash::verify_stored(&credential, "wrongpassword"), Ok(false));
+    /// ```
+    pub fn verify_stored(
+        credential: &StoredCredential,
+        password: &str,
+    ) -> Result<bool, HshError> {
+        Self::verify_components(
+            &credential.hash,
+            &credential.salt,
+            credential.algorithm,
+            &credential.params,
+            password,
+        )
+    }
+
+    /// A function that verifies a password like `verify`, on the Tokio
+    /// blocking thread pool.
+    ///
+    /// Verification takes long enough to stall an async executor, so
+    /// async code should use this instead of `verify`. It must be
+    /// called from within a Tokio runtime.
+    ///
+    /// The password has to be owned to cross into the blocking thread,
+    /// so a borrowed password is copied once, and an owned one is
+    /// moved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let verified = hash.verify_async(String::from("password123")).await;
+    ///
+    /// assert_eq!(verified, Ok(true));
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    // Anonymous lifetimes are not allowed in `impl Trait` arguments.
+    #[allow(single_use_lifetimes)]
+    pub async fn verify_async<'a>(
+        &self,
+        password: impl Into<Cow<'a, str>>,
+    ) -> Result<bool, HshError> {
+        let password = password.into().into_owned();
+        let hash = self.clone();
+        tokio::task::spawn_blocking(move || hash.verify(&password))
+            .await
+            .map_err(|e| HshError::Backend(e.to_string()))?
+    }
+
+    /// A function that verifies a password like `verify`, and calls
+    /// `hook` with the outcome.
+    ///
+    /// This lets callers count failed attempts, for example to feed a
+    /// rate limiter or trigger a lockout, without re-inspecting the
+    /// result. The hook is called exactly once when verification
+    /// completes, and not at all when it returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let mut failures = 0;
+    ///
+    /// let verified = hash.verify_with_hook("wrongpassword", &mut |ok| {
+    ///     if !ok {
+    ///         failures += 1;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(verified, Ok(false));
+    /// assert_eq!(failures, 1);
+    /// ```
+    pub fn verify_with_hook(
+        &self,
+        password: &str,
+        hook: &mut dyn FnMut(bool),
+    ) -> Result<bool, HshError> {
+        let verified = self.verify(password)?;
+        hook(verified);
+        Ok(verified)
+    }
+
+    /// A function that verifies a password like `verify`, and logs the
+    /// outcome for `subject`, such as a username, to the
+    /// `AUDIT_LOG_TARGET` log target.
+    ///
+    /// Only a fingerprint of the subject is logged: the first 16 hex
+    /// characters of its SHA-256. Attempts on the same subject can be
+    /// correlated without writing the subject itself to the logs. The
+    /// fingerprint is unkeyed, so a guessable subject can still be
+    /// confirmed by hashing candidates; it is pseudonymous, not
+    /// anonymous. The password is never logged.
+    ///
+    /// Outcomes are logged at `Info` level, errors at `Warn` level.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.verify_with_subject("password123", "alice"), Ok(true));
+    /// ```
+    pub fn verify_with_subject(
+        &self,
+        password: &str,
+        subject: &str,
+    ) -> Result<bool, HshError> {
+        let subject =
+            encode_hex(&Sha256::digest(subject.as_bytes())[..8]);
+        let result = self.verify(password);
+        match &result {
+            Ok(verified) => log::info!(
+                target: AUDIT_LOG_TARGET,
+                "verification for subject {} {}",
+                subject,
+                if *verified { "succeeded" } else { "failed" }
+            ),
+            Err(e) => log::warn!(
+                target: AUDIT_LOG_TARGET,
+                "verification for subject {} returned an error: {}",
+                subject,
+                e
+            ),
+        }
+        result
+    }
+
+    /// A function that verifies a password like `verify`, falling back
+    /// to the password without trailing whitespace.
+    ///
+    /// This is a migration aid for hashes created by systems that
+    /// trimmed passwords, or for frontends that append a newline or a
+    /// space. The verbatim password is tried first; the trimmed one
+    /// only if it does not match.
+    ///
+    /// **Warning:** this weakens every password it is used for, since
+    /// several inputs are accepted for each, and it doubles the cost
+    /// of failed attempts. Prefer `verify`, and rehash with the
+    /// verbatim password after a successful login.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.verify("password123\n"), Ok(false));
+    /// assert_eq!(hash.verify_trimmed("password123\n"), Ok(true));
+    /// ```
+    pub fn verify_trimmed(
+        &self,
+        password: &str,
+    ) -> Result<bool, HshError> {
+        if self.verify(password)? {
+            return Ok(true);
+        }
+        let trimmed = password.trim_end();
+        if trimmed.len() == password.len() {
+            return Ok(false);
+        }
+        self.verify(trimmed)
+    }
+
+    /// A function that verifies a password like `verify`, after
+    /// applying the transform the password was hashed with.
+    ///
+    /// This verifies hashes from systems that peppered passwords by
+    /// prehashing them, for example with `HMAC-SHA256(pepper,
+    /// password)`. See `Prehash` for the supported transforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, prehash::Prehash};
+    ///
+    /// let prehash = Prehash::HmacSha256 { key: b"pepper".to_vec() };
+    /// let stored = prehash.apply("password123").unwrap();
+    /// let hash = Hash::new(&stored, "somesalt", "argon2i").unwrap();
+    ///
+    /// assert_eq!(hash.verify_prehashed("password123", prehash), Ok(true));
+    /// assert_eq!(hash.verify("password123"), Ok(false));
+    /// ```
+    pub fn verify_prehashed(
+        &self,
+        password: &str,
+        prehash: Prehash,
+    ) -> Result<bool, HshError> {
+        self.verify(&prehash.apply(password)?)
+    }
+
+    /// A function that verifies a password read from `reader`, such as
+    /// standard input or a key file, without going through a `String`.
+    ///
+    /// The reader is read to its end and the bytes are verified as is
+    /// with `verify_bytes`; a trailing newline is not stripped. Input
+    /// longer than `MAX_PASSWORD_LEN` bytes is rejected with
+    /// `HshError::InvalidParams`, and read errors are reported as
+    /// `HshError::Io`. The buffer is zeroized before returning.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    /// use std::io::Cursor;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.verify_reader(Cursor::new("password123")), Ok(true));
+    /// ```
+    pub fn verify_reader(
+        &self,
+        reader: impl Read,
+    ) -> Result<bool, HshError> {
+        self.verify_bytes(&read_password(reader)?)
+    }
+
+    /// A function that verifies a password like `verify`, after
+    /// normalizing it to Unicode NFKC.
+    ///
+    /// This checks hashes made with `new_normalized`; see there.
+    #[cfg(feature = "unicode")]
+    pub fn verify_normalized(
+        &self,
+        password: &str,
+    ) -> Result<bool, HshError> {
+        self.verify(password.nfkc().collect::<String>())
+    }
+
+    /// A function that verifies a password like `verify`, after
+    /// passing it through `prehash`.
+    ///
+    /// This is for peppers kept in an HSM, where only the HSM can
+    /// compute `HMAC(pepper, password)`: the closure performs that call
+    /// and returns the bytes that were hashed, so the pepper never
+    /// reaches this library. The result is verified as with
+    /// `verify_bytes`, so for Bcrypt it must be valid UTF-8, such as a
+    /// hex encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, prehash::Prehash};
+    ///
+    /// // Stands in for an HSM holding the pepper.
+    /// let hsm = |password: &[u8]| {
+    ///     let password = std::str::from_utf8(password).unwrap();
+    ///     let prehash = Prehash::HmacSha256 { key: b"pepper".to_vec() };
+    ///     prehash.apply(password).unwrap().into_bytes()
+    /// };
+    /// let stored = String::from_utf8(hsm(b"password123")).unwrap();
+    /// let hash = Hash::new(&stored, "somesalt", "argon2i").unwrap();
+    ///
+    /// assert_eq!(hash.verify_with_prehash_fn("password123", hsm), Ok(true));
+    /// ```
+    pub fn verify_with_prehash_fn(
+        &self,
+        password: &str,
+        prehash: impl Fn(&[u8]) -> Vec<u8>,
+    ) -> Result<bool, HshError> {
+        let mut prehashed = prehash(password.as_bytes());
+        let verified = self.verify_bytes(&prehashed);
+        prehashed.zeroize();
+        verified
+    }
+
+    /// A function that verifies a password against a stored string in
+    /// either of the formats this library has emitted: a PHC string,
+    /// or the legacy `salt:hex` form of `to_string_representation`.
+    ///
+    /// Strings starting with `$` are parsed as PHC strings, whose own
+    /// algorithm takes precedence. Any other string is read as
+    /// `salt:hex`, split at the last `:`, and verified with `algo`.
+    /// That form records no parameters, so the legacy parameters are
+    /// tried first, then the current defaults when they differ (Argon2i
+    /// hashes from before and after the move to Argon2 version 0x13).
+    /// A wrong password then costs two derivations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let legacy = hash.to_string_representation();
+    /// let phc = hash.to_phc_string().unwrap();
+    ///
+    /// assert_eq!(Hash::verify_auto_format(&legacy, "password123", "scrypt"), Ok(true));
+    /// assert_eq!(Hash::verify_auto_format(&phc, "password123", "scrypt"), Ok(true));
+    /// ```
+    pub fn verify_auto_format(
+        stored: &str,
+        password: &str,
+        algo: &str,
+    ) -> Result<bool, HshError> {
+        if stored.starts_with('$') {
+            return Self::parse_phc(stored)?.verify(password);
+        }
+
+        let algorithm =
+            HashAlgorithm::from_str(algo).map_err(|_| {
+                HshError::UnsupportedAlgorithm(algo.to_string())
+            })?;
+        let (salt, digest) = stored
+            .rsplit_once(':')
+            .ok_or(HshError::InvalidHashString)?;
+        let hash = SaltEncoding::Hex
+            .decode(digest.as_bytes())
+            .map_err(|_| {
+                HshError::CorruptStoredHash(String::from(
+                    "digest is not valid hex",
+                ))
+            })?;
+        let mut legacy = Hash {
+            hash,
+            salt: salt.as_bytes().to_vec(),
+            algorithm,
+            params: None,
+            original_phc: None,
+            salt_source: SaltSource::Unknown,
+        };
+        if legacy.verify(password)? {
+            return Ok(true);
+        }
+
+        let defaults = Params::default_for(algorithm);
+        if defaults == Params::legacy(algorithm) {
+            return Ok(false);
+        }
+        legacy.params = Some(defaults);
+        legacy.verify(password)
+    }
+
+    /// A function that verifies a password like `verify`, after
+    /// re-interpreting the stored salt under the given encoding.
+    ///
+    /// This bridges hashes created from an encoded salt string (for
+    /// example hex) while the salt is now stored as raw bytes, and
+    /// helps diagnose such mismatches. Bcrypt embeds its own salt in
+    /// the hash, so the encoding has no effect on it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, salt_encoding::SaltEncoding};
+    ///
+    /// let mut hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// // The salt as stored by a system that kept it hex encoded.
+    /// hash.salt = b"736f6d6573616c74".to_vec();
+    ///
+    /// assert_eq!(hash.verify("password123"), Ok(false));
+    /// assert_eq!(
+    ///     hash.verify_with_salt_encoding("password123", SaltEncoding::Hex),
+    ///     Ok(true)
+    /// );
+    /// ```
+    pub fn verify_with_salt_encoding(
+        &self,
+        password: &str,
+        enc: SaltEncoding,
+    ) -> Result<bool, HshError> {
+        #[cfg(feature = "algo-bcrypt")]
+        if self.algorithm == HashAlgorithm::Bcrypt {
+            return self.verify(password);
+        }
+        let mut reinterpreted = self.clone();
+        reinterpreted.salt = enc.decode(&self.salt)?;
+        reinterpreted.verify(password)
+    }
+
+    /// A function that verifies a password like `verify`, with the
+    /// parameters supplied by `source` instead of those stored on the
+    /// hash.
+    ///
+    /// This suits setups where parameters live in a configuration
+    /// service rather than alongside the hash bytes: `source` is
+    /// called with the hash algorithm at verification time. Parameters
+    /// that do not belong to the algorithm are rejected with
+    /// `HshError::InvalidParams`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash::Hash, params::Params};
+    ///
+    /// let mut hash =
+    ///     Hash::new_scrypt_with_params("password123", b"somesalt".to_vec(), 10, 8, 1, 64)
+    ///         .unwrap();
+    /// hash.params = None;
+    ///
+    /// let source = |_| Params::Scrypt { log_n: 10, r: 8, p: 1 };
+    /// assert_eq!(hash.verify_with_param_source("password123", source), Ok(true));
+    /// ```
+    pub fn verify_with_param_source(
+        &self,
+        password: &str,
+        source: impl Fn(HashAlgorithm) -> Params,
+    ) -> Result<bool, HshError> {
+        let params = source(self.algorithm);
+        if !params.matches(self.algorithm) {
+            return Err(HshError::InvalidParams(format!(
+                "parameters do not belong to {}",
+                self.algorithm
+            )));
+        }
+        let mut sourced = self.clone();
+        sourced.params = Some(params);
+        sourced.verify(password)
+    }
+
+    /// A function that verifies a password like `verify`, but does
+    /// not return before `floor` has elapsed.
+    ///
+    /// When an application stores hashes from several algorithms,
+    /// the time `verify` takes reveals which algorithm a user's hash
+    /// uses. Padding every verification up to a fixed floor, chosen to
+    /// exceed the slowest algorithm in use, hides that difference at
+    /// the cost of slower logins. Errors are padded as well, so a
+    /// corrupt or malformed hash, which fails before any key
+    /// derivation, cannot be told apart by timing either.
+    ///
+    /// This is opt-in: `verify` itself never sleeps. If verification
+    /// takes longer than `floor`, the result is returned immediately.
     ///
     /// # Example
     ///
     /// ```
     /// use hsh::models::hash::Hash;
+    /// use std::time::Duration;
     ///
-    /// let password = "my_password";
-    /// let cost: u32 = 16;
+    /// let hash = Hash::new_bcrypt("password123", 4).unwrap();
+    /// let verified = hash
+    ///     .verify_constant_time_across_algorithms(
+    ///         "password123",
+    ///         Duration::from_millis(50),
+    ///     )
+    ///     .unwrap();
     ///
-    /// let result = Hash::new_bcrypt(password, cost);
-    /// match result {
-    ///     Ok(hash) => println!("Successfully created Bcrypt hash"),
-    ///     Err(e) => println!("An error occurred: {}", e),
-    /// }
+    /// assert!(verified);
     /// ```
-    pub fn new_bcrypt(
+    pub fn verify_constant_time_across_algorithms(
+        &self,
         password: &str,
-        cost: u32,
-    ) -> Result<Self, String> {
-        // Perform Bcrypt hashing
-        let hashed_password =
-            bcrypt::hash(password, cost).map_err(|e| {
-                format!("Failed to hash password with Bcrypt: {}", e)
-            })?;
-
-        // In Bcrypt, the salt is embedded in the hashed password.
-        // So, you can just use an empty salt when building the Hash object.
-        let empty_salt = Vec::new();
-
-        HashBuilder::new()
-            .hash(hashed_password.as_bytes().to_vec())
-            .salt(empty_salt)
-            .algorithm(HashAlgorithm::Bcrypt)
-            .build()
+        floor: Duration,
+    ) -> Result<bool, HshError> {
+        let start = Instant::now();
+        let result = self.verify(password);
+        if let Some(remaining) = floor.checked_sub(start.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+        result
     }
 
-    /// Creates a new `Hash` instance using Scrypt algorithm for password hashing.
+    /// A function that verifies a password like `verify`, calling
+    /// `beat` periodically until verification completes.
+    ///
+    /// Key derivation cannot report its own progress, so this spawns a
+    /// thread that runs `verify` while the calling thread calls `beat`
+    /// every `HEARTBEAT_INTERVAL`, letting an interactive caller render
+    /// a spinner during verifications with very large parameters.
+    /// `beat` is not called if verification finishes within the first
+    /// interval. A panic in the verifying thread is returned as an
+    /// `HshError::Backend`.
     ///
     /// # Example
     ///
     /// ```
-    /// use hsh::models::hash::{Hash, Salt};
+    /// use hsh::models::hash::Hash;
     ///
-    /// let password = "my_password";
-    /// let salt: Salt = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let verified = hash
+    ///     .verify_with_heartbeat("password123", || eprint!("."))
+    ///     .unwrap();
     ///
-    /// let result = Hash::new_scrypt(password, salt);
-    /// match result {
-    ///     Ok(hash) => println!("Successfully created Scrypt hash"),
-    ///     Err(e) => println!("An error occurred: {}", e),
-    /// }
+    /// assert!(verified);
     /// ```
-    pub fn new_scrypt(
+    pub fn verify_with_heartbeat(
+        &self,
         password: &str,
-        salt: Salt,
-    ) -> Result<Self, String> {
-        // Convert the Vec<u8> salt to a &str for hashing
-        let salt_str = std::str::from_utf8(&salt)
-            .map_err(|_| "Failed to convert salt to string")?;
-
-        // Perform Scrypt hashing using a wrapper function that sets the parameters
-        let calculated_hash =
-            Scrypt::hash_password(password, salt_str)?;
-
-        // Use the builder pattern to construct the Hash instance
-        HashBuilder::new()
-            .hash(calculated_hash)
-            .salt(salt)
-            .algorithm(HashAlgorithm::Scrypt)
-            .build()
-    }
-
-    /// A function that returns the hash algorithm used by the hash map.
-    pub fn algorithm(&self) -> HashAlgorithm {
-        self.algorithm
-    }
-
-    /// A function that creates a new hash object from a hash value and a hash algorithm.
-    pub fn from_hash(hash: &[u8], algo: &str) -> Result<Self, String> {
-        let algorithm = match algo {
-            "argon2i" => Ok(HashAlgorithm::Argon2i),
-            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
-            "scrypt" => Ok(HashAlgorithm::Scrypt),
-            _ => Err(format!("Unsupported hash algorithm: {}", algo)),
-        }?;
-
-        Ok(Hash {
-            salt: Vec::new(),
-            hash: hash.to_vec(),
-            algorithm,
-        })
+        beat: impl Fn(),
+    ) -> Result<bool, HshError> {
+        run_with_heartbeat(|| self.verify(password), beat)
     }
 
-    /// A function that creates a new hash object from a hash string in the format algorithm$salt$hash.
-    pub fn from_string(hash_str: &str) -> Result<Self, String> {
-        // Split the hash string into six parts, using the `$` character as the delimiter.
-        let parts: Vec<&str> = hash_str.split('$').collect();
-
-        // If the hash string does not contain six parts, return an error.
-        if parts.len() != 6 {
-            return Err(String::from("Invalid hash string"));
-        }
-
-        // Parse the algorithm from the first part of the hash string.
-        let algorithm = Self::parse_algorithm(hash_str)?;
-
-        // Parse the salt from the second, third, fourth, and fifth parts of the hash string.
-        let salt = format!(
-            "${}${}${}${}",
-            parts[1], parts[2], parts[3], parts[4]
-        );
-
-        // Decode the hash bytes from the sixth part of the hash string.
-        let hash_bytes =
-            general_purpose::STANDARD.decode(parts[5]).map_err(
-                |_| format!("Failed to decode base64: {}", parts[5]),
-            )?;
-
-        // Create the `Hash` object and return it.
-        Ok(Hash {
-            salt: salt.into_bytes(),
-            hash: hash_bytes,
-            algorithm,
-        })
+    /// Returns `true` if the stored digest is in `filter`, a set of
+    /// digests known to be compromised.
+    ///
+    /// This is best-effort: only this exact hash can match, since the
+    /// digest depends on the salt and parameters as well as the
+    /// password. A `false` result does not mean the password is safe.
+    /// The password is not needed and nothing is recomputed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{compromised_filter::CompromisedFilter, hash::Hash};
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    /// let mut filter = CompromisedFilter::new();
+    /// assert!(!hash.is_compromised(&filter));
+    ///
+    /// filter.insert(hash.hash.clone());
+    /// assert!(hash.is_compromised(&filter));
+    /// ```
+    pub fn is_compromised(&self, filter: &CompromisedFilter) -> bool {
+        filter.contains(&self.hash)
     }
 
-    /// A function that generates a hash value for a password using the specified hash algorithm.
-    /// The function takes three arguments:
+    /// A function that verifies a password like `verify`, retrying
+    /// when the backend fails transiently, for example when memory
+    /// cannot be allocated under load.
     ///
-    /// - password: The password to be hashed.
-    /// - salt: A random string used to make the hash value unique.
-    /// - algo: The name of the hash algorithm to use.
+    /// Only `HshError::Backend` is retried: it is reserved for failures
+    /// of the backend itself, such as a random number generator or an
+    /// allocation failing, and never reports a problem with the input.
+    /// A mismatch is `Ok(false)` and is returned at once, as are all
+    /// other errors, which fail the same way every time:
+    /// `CorruptStoredHash`, `InvalidParams` (including parameters or
+    /// lengths the backend rejects), `SaltTooShort`, `NonUtf8Password`
+    /// and the rest.
     ///
-    /// The function returns a `Result` object containing the hash value if successful, or an error message if unsuccessful.
-    pub fn generate_hash(
+    /// The built-in verifiers currently have no transient failure
+    /// modes, so with `verify` a retry only happens for errors a future
+    /// backend may report; `verify_with_retry_using` can supply a
+    /// verifier that does fail transiently.
+    ///
+    /// `attempts` is the total number of tries, including the first;
+    /// `0` is treated as `1`. Retry `n` waits `n * RETRY_BACKOFF`. The
+    /// last error is returned if every attempt fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash::Hash;
+    ///
+    /// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+    ///
+    /// assert_eq!(hash.verify_with_retry("password123", 3), Ok(true));
+    /// assert_eq!(hash.verify_with_retry("password456", 3), Ok(false));
+    /// ```
+    pub fn verify_with_retry(
+        &self,
         password: &str,
-        salt: &str,
-        algo: &str,
-    ) -> Result<Vec<u8>, String> {
-        match algo {
-            "argon2i" => Argon2i::hash_password(password, salt),
-            "bcrypt" => Bcrypt::hash_password(password, salt),
-            "scrypt" => Scrypt::hash_password(password, salt),
-            _ => Err(format!("Unsupported hash algorithm: {}", algo)),
-        }
-    }
-
-    /// A function that generates a random string of the specified length.
-    pub fn generate_random_string(len: usize) -> String {
-        let mut rng = Random::default();
-        let chars = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
-        (0..len)
-            .map(|_| {
-                chars
-                    .chars()
-                    .nth(rng.random_range(0, chars.len() as u32)
-                        as usize)
-                    .unwrap()
-            })
-            .collect()
+        attempts: u32,
+    ) -> Result<bool, HshError> {
+        self.verify_with_retry_using(
+            password,
+            attempts,
+            |hash, password| hash.verify(password),
+        )
     }
 
-    /// A function that generates a random salt for a password using the specified hash algorithm.
-    pub fn generate_salt(algo: &str) -> Result<String, String> {
-        let mut rng = Random::default();
-        match algo {
-            "argon2i" => Ok(Self::generate_random_string(16)),
-            "bcrypt" => {
-                let salt: Vec<u8> = rng.bytes(16);
-                let salt_array: [u8; 16] =
-                    salt.try_into().map_err(|_| {
-                        "Error: failed to convert salt to an array"
-                    })?;
-                Ok(general_purpose::STANDARD.encode(&salt_array[..]))
-            }
-            "scrypt" => {
-                let salt: Vec<u8> = rng.bytes(32);
-                let salt_array: [u8; 32] =
-                    salt.try_into().map_err(|_| {
-                        "Error: failed to convert salt to an array"
-                    })?;
-                Ok(general_purpose::STANDARD.encode(&salt_array[..]))
+    /// A function that behaves like `verify_with_retry`, but checks the
+    /// password with `verifier` instead of `verify`.
+    ///
+    /// This lets callers wrap the backend, for example to add metrics,
+    /// and lets tests inject transient failures.
+    pub fn verify_with_retry_using(
+        &self,
+        password: &str,
+        attempts: u32,
+        mut verifier: impl FnMut(&Self, &str) -> Result<bool, HshError>,
+    ) -> Result<bool, HshError> {
+        let attempts = attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match verifier(self, password) {
+                Err(HshError::Backend(_)) if attempt < attempts => {
+                    std::thread::sleep(RETRY_BACKOFF * attempt);
+                    attempt += 1;
+                }
+                result => return result,
             }
-            _ => Err(format!("Unsupported hash algorithm: {}", algo)),
         }
     }
 
-    /// A function that returns the hash value of a hash object.
-    pub fn hash(&self) -> &[u8] {
-        &self.hash
-    }
-
-    /// A function that returns the length of the hash value of a hash object.
-    pub fn hash_length(&self) -> usize {
-        self.hash.len()
+    /// Creates a new `Hash` from a password given as bytes, with the
+    /// given parameters and the default digest length of the
+    /// algorithm.
+    fn new_with_params(
+        password: &[u8],
+        salt: Salt,
+        algorithm: HashAlgorithm,
+        params: Params,
+        policy: &PasswordPolicy,
+    ) -> Result<Self, HshError> {
+        Self::new_with_params_sized(
+            password, salt, algorithm, params, policy, None,
+        )
     }
 
-    /// A function that creates a new hash object from a password, salt, and hash algorithm.
-    pub fn new(
-        password: &str,
-        salt: &str,
-        algo: &str,
-    ) -> Result<Self, String> {
-        // Enforce a minimum password length of 8 characters.
-        if password.len() < 8 {
-            return Err(String::from("Password is too short. It must be at least 8 characters."));
+    /// Creates a new `Hash` like `new_with_params`, with a digest of
+    /// `output_len` bytes instead of the default length (32 for Argon2,
+    /// 64 for Scrypt). Bcrypt digests have a fixed length, so
+    /// `output_len` does not apply to them.
+    ///
+    /// Bcrypt generates its own salt, so with only `algo-bcrypt`
+    /// enabled every path returns from the `match`.
+    #[cfg_attr(
+        not(any(feature = "algo-argon2", feature = "algo-scrypt")),
+        allow(unreachable_code, unused_variables)
+    )]
+    fn new_with_params_sized(
+        password: &[u8],
+        salt: Salt,
+        algorithm: HashAlgorithm,
+        params: Params,
+        policy: &PasswordPolicy,
+        output_len: Option<usize>,
+    ) -> Result<Self, HshError> {
+        if !params.matches(algorithm) {
+            return Err(HshError::InvalidParams(format!(
+                "parameters do not belong to {}",
+                algorithm
+            )));
         }
-        let hash = Self::generate_hash(password, salt, algo)?;
-
-        let algorithm = match algo {
-            "argon2i" => Ok(HashAlgorithm::Argon2i),
-            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
-            "scrypt" => Ok(HashAlgorithm::Scrypt),
-            _ => Err(format!("Unsupported hash algorithm: {}", algo)),
-        }?;
-
-        Ok(Self {
+        check_minimums(algorithm, policy, password, &salt)?;
+        let hash = match params {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                let mut output = vec![0u8; output_len.unwrap_or(32)];
+                argon2_hash_with_params(algorithm)(
+                    password,
+                    &salt,
+                    version,
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                    &mut output,
+                )?;
+                output
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } => {
+                let password = std::str::from_utf8(password)
+                    .map_err(|_| HshError::NonUtf8Password)?;
+                return Self::new_bcrypt(password, cost);
+            }
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { log_n, r, p } => {
+                let mut output = vec![0u8; output_len.unwrap_or(64)];
+                Scrypt::hash_with_params(
+                    password,
+                    &salt,
+                    log_n,
+                    r,
+                    p,
+                    &mut output,
+                )?;
+                output
+            }
+        };
+        metrics::record_hash();
+        Ok(Hash {
             hash,
-            salt: salt.as_bytes().to_vec(),
+            salt,
             algorithm,
+            params: Some(params),
+            original_phc: None,
+            salt_source: SaltSource::Provided,
         })
     }
 
-    /// A function that parses a JSON string into a hash object.
-    pub fn parse(
-        input: &str,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        let hash: Hash = serde_json::from_str(input)?;
-        Ok(hash)
+    /// Derives the digest of a password again, with the stored salt
+    /// and the given parameters, for algorithms that verify by
+    /// `VerifyMethod::Recompute`.
+    fn recompute(
+        &self,
+        password: &[u8],
+        params: Params,
+    ) -> Result<Zeroizing<Vec<u8>>, HshError> {
+        self.recompute_with_salt(password, &self.salt, params)
     }
 
-    /// A function that parses a hash string into a hash algorithm.
-    pub fn parse_algorithm(
-        hash_str: &str,
-    ) -> Result<HashAlgorithm, String> {
-        let parts: Vec<&str> = hash_str.split('$').collect();
-        if parts.len() < 2 {
-            return Err(String::from("Invalid hash string"));
-        }
-        match parts[1] {
-            "argon2i" => Ok(HashAlgorithm::Argon2i),
-            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
-            "scrypt" => Ok(HashAlgorithm::Scrypt),
-            _ => {
-                Err(format!("Unsupported hash algorithm: {}", parts[1]))
+    /// Derives the digest of a password like `recompute`, with `salt`
+    /// in place of the stored salt.
+    #[cfg_attr(
+        not(any(feature = "algo-argon2", feature = "algo-scrypt")),
+        allow(unused_variables)
+    )]
+    fn recompute_with_salt(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+        params: Params,
+    ) -> Result<Zeroizing<Vec<u8>>, HshError> {
+        match params {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                self.check_stored_format_with_salt(salt)?;
+
+                let mut output =
+                    Zeroizing::new(vec![0u8; self.hash.len()]);
+                argon2_hash_with_params(self.algorithm)(
+                    password,
+                    salt,
+                    version,
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                    &mut output,
+                )?;
+                Ok(output)
             }
-        }
-    }
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { log_n, r, p } => {
+                self.check_stored_format_with_salt(salt)?;
 
-    /// A function that returns the salt used to hash a password.
-    pub fn salt(&self) -> &[u8] {
-        &self.salt
+                let mut output =
+                    Zeroizing::new(vec![0u8; self.hash.len()]);
+                Scrypt::hash_with_params(
+                    password,
+                    salt,
+                    log_n,
+                    r,
+                    p,
+                    &mut output,
+                )?;
+                Ok(output)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(HshError::InvalidParams(format!(
+                "{} does not verify by recomputation",
+                self.algorithm
+            ))),
+        }
     }
 
-    /// A function that sets the hash value of a hash object.
-    pub fn set_hash(&mut self, hash: &[u8]) {
-        self.hash = hash.to_vec();
-    }
+    /// Checks a password in the backend, for algorithms that verify by
+    /// `VerifyMethod::Backend`.
+    #[cfg_attr(not(feature = "algo-bcrypt"), allow(unused_variables))]
+    fn verify_in_backend(
+        &self,
+        password: &[u8],
+        params: Params,
+    ) -> Result<bool, HshError> {
+        match params {
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { .. } => {
+                self.check_stored_format()?;
+                let hash_str = std::str::from_utf8(&self.hash)
+                    .map_err(|_| {
+                        HshError::CorruptStoredHash(String::from(
+                            "Bcrypt hash is not valid UTF-8",
+                        ))
+                    })?;
+                let password = std::str::from_utf8(password)
+                    .map_err(|_| HshError::NonUtf8Password)?;
 
-    /// A function that sets the password of a hash object.
-    pub fn set_password(
-        &mut self,
-        password: &str,
-        salt: &str,
-        algo: &str,
-    ) -> Result<(), String> {
-        self.hash = Self::generate_hash(password, salt, algo)?;
-        Ok(())
+                // `bcrypt::verify` compares in constant time and only
+                // fails when the stored hash cannot be parsed (bad
+                // prefix, cost or base64).
+                bcrypt::verify(password, hash_str).map_err(|e| {
+                    HshError::CorruptStoredHash(format!(
+                        "Bcrypt hash could not be parsed: {}",
+                        e
+                    ))
+                })
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(HshError::InvalidParams(format!(
+                "{} does not verify in its backend",
+                self.algorithm
+            ))),
+        }
     }
 
-    /// A function that sets the salt of a hash object.
-    pub fn set_salt(&mut self, salt: &[u8]) {
-        self.salt = salt.to_vec();
+    /// Returns the parameters to verify with: the recorded parameters,
+    /// or the legacy parameters when none were recorded.
+    pub(crate) fn stored_params(&self) -> Result<Params, HshError> {
+        match self.params {
+            Some(params) if params.matches(self.algorithm) => {
+                Ok(params)
+            }
+            Some(_) => Err(HshError::CorruptStoredHash(format!(
+                "parameters do not belong to {}",
+                self.algorithm
+            ))),
+            None => Ok(Params::legacy(self.algorithm)),
+        }
     }
 
-    /// A function that converts a hash object to a string representation.
-    pub fn to_string_representation(&self) -> String {
-        let hash_str = self
-            .hash
-            .iter()
-            .map(|b| format!("{:02x}", b))
-            .collect::<Vec<String>>()
-            .join("");
-
-        format!("{}:{}", String::from_utf8_lossy(&self.salt), hash_str)
+    /// Checks that the stored hash and salt have a shape the algorithm
+    /// can produce, before any expensive work is done to verify them.
+    ///
+    /// Only stored values are looked at, so the time this takes does
+    /// not depend on the password. A Bcrypt hash holding the PHC string
+    /// of another algorithm is reported as `HshError::AlgorithmMismatch`,
+    /// and other malformed values as `HshError::CorruptStoredHash`.
+    fn check_stored_format(&self) -> Result<(), HshError> {
+        self.check_stored_format_with_salt(&self.salt)
     }
 
-    /// A function that verifies a password against a hash object.
-    pub fn verify(&self, password: &str) -> Result<bool, &'static str> {
-        let salt = std::str::from_utf8(&self.salt)
-            .map_err(|_| "Failed to convert salt to string")?;
-
+    /// Checks the stored hash like `check_stored_format`, with `salt`
+    /// in place of the stored salt.
+    #[cfg_attr(not(feature = "algo-argon2"), allow(unused_variables))]
+    fn check_stored_format_with_salt(
+        &self,
+        salt: &[u8],
+    ) -> Result<(), HshError> {
         match self.algorithm {
-            HashAlgorithm::Argon2i => {
-                // Hash the password once
-                let calculated_hash =
-                    argon2i_simple(password, salt).to_vec();
-
-                // Debugging information
-                println!("Algorithm: Argon2i");
-                println!(
-                    "Provided password for verification: {}",
-                    password
-                );
-                println!("Salt used for verification: {}", salt);
-                println!("Calculated Hash: {:?}", calculated_hash);
-                println!("Stored Hash: {:?}", self.hash);
-
-                // Perform the verification
-                Ok(calculated_hash == self.hash)
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i | HashAlgorithm::Argon2id => {
+                if salt.len() < self.algorithm.min_salt_len() {
+                    return Err(HshError::CorruptStoredHash(format!(
+                        "{} salt must be at least {} bytes, found {}",
+                        self.algorithm,
+                        self.algorithm.min_salt_len(),
+                        salt.len()
+                    )));
+                }
+                self.check_stored_hash_len(ARGON2I_HASH_LEN_RANGE)
+            }
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => {
+                self.check_stored_hash_len(SCRYPT_HASH_LEN_RANGE)
             }
+            #[cfg(feature = "algo-bcrypt")]
             HashAlgorithm::Bcrypt => {
-                // Debugging information
-                println!("Algorithm: Bcrypt");
-                println!(
-                    "Provided password for verification: {}",
-                    password
-                );
-
                 let hash_str = std::str::from_utf8(&self.hash)
-                    .map_err(|_| "Failed to convert hash to string")?;
-                bcrypt::verify(password, hash_str)
-                    .map_err(|_| "Failed to verify Bcrypt password")
+                    .map_err(|_| {
+                        HshError::CorruptStoredHash(String::from(
+                            "Bcrypt hash is not valid UTF-8",
+                        ))
+                    })?;
+                let tag = hash_str
+                    .strip_prefix('$')
+                    .and_then(|rest| rest.split('$').next())
+                    .and_then(|tag| {
+                        HashAlgorithm::from_phc_tag(tag).ok()
+                    });
+                if let Some(found) =
+                    tag.filter(|found| *found != self.algorithm)
+                {
+                    return Err(HshError::AlgorithmMismatch {
+                        expected: self.algorithm,
+                        found,
+                    });
+                }
+                // The same layout checks as `phc::parse`, so that a
+                // malformed string never reaches `bcrypt::verify`.
+                phc::parse(hash_str).map(drop).map_err(|e| {
+                    HshError::CorruptStoredHash(format!(
+                        "Bcrypt hash could not be parsed: {}",
+                        e
+                    ))
+                })
             }
-            HashAlgorithm::Scrypt => {
-                // Debugging information
-                println!("Algorithm: Scrypt");
-                println!(
-                    "Provided password for verification: {}",
-                    password
-                );
-                println!("Salt used for verification: {}", salt);
+        }
+    }
 
-                let scrypt_params = scrypt::Params::new(14, 8, 1, 64)
-                    .map_err(|_| {
-                    "Failed to create Scrypt params"
-                })?;
-                let mut output = [0u8; 64];
-                match scrypt(
-                    password.as_bytes(),
-                    salt.as_bytes(),
-                    &scrypt_params,
-                    &mut output,
-                ) {
-                    Ok(_) => {
-                        println!(
-                            "Calculated Hash: {:?}",
-                            output.to_vec()
-                        );
-                        println!("Stored Hash: {:?}", self.hash);
-                        Ok(output.to_vec() == self.hash)
-                    }
-                    Err(_) => Err("Scrypt hashing failed"),
+    /// Checks that the stored digest has a length the algorithm can
+    /// produce, returning a corruption error otherwise.
+    #[cfg(any(feature = "algo-argon2", feature = "algo-scrypt"))]
+    fn check_stored_hash_len(
+        &self,
+        expected: std::ops::RangeInclusive<usize>,
+    ) -> Result<(), HshError> {
+        if !expected.contains(&self.hash.len()) {
+            return Err(HshError::CorruptStoredHash(format!(
+                "{} hash must be {} to {} bytes, found {}",
+                self.algorithm,
+                expected.start(),
+                expected.end(),
+                self.hash.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Runs `work` on a scoped thread, calling `beat` every
+/// `HEARTBEAT_INTERVAL` on the calling thread until it completes.
+///
+/// A panic in `work` is reported as an `HshError::Backend` instead of
+/// being propagated.
+fn run_with_heartbeat<T: Send>(
+    work: impl FnOnce() -> Result<T, HshError> + Send,
+    beat: impl Fn(),
+) -> Result<T, HshError> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        let worker = scope.spawn(move || {
+            // The receiver outlives the thread, so this cannot fail.
+            let _ = sender.send(work());
+        });
+        loop {
+            match receiver.recv_timeout(HEARTBEAT_INTERVAL) {
+                Ok(result) => return result,
+                Err(RecvTimeoutError::Timeout) => beat(),
+                Err(RecvTimeoutError::Disconnected) => {
+                    // The sender is only dropped unsent when `work`
+                    // panics. Joining the thread takes the panic,
+                    // which the scope would otherwise raise again.
+                    let _ = worker.join();
+                    return Err(HshError::Backend(String::from(
+                        "verification thread panicked",
+                    )));
                 }
             }
         }
+    })
+}
+
+/// Normalizes an algorithm name before matching it against the
+/// canonical keys (`argon2i`, `bcrypt`, `scrypt`), so that `"Argon2i"`
+/// or `"bcrypt "` are accepted too.
+fn normalize_algorithm(algo: &str) -> String {
+    algo.trim().to_ascii_lowercase()
+}
+
+/// Displays the PHC string of a `Hash`.
+///
+/// Returned by `Hash::display_phc`.
+#[derive(Clone, Copy, Debug)]
+pub struct PhcDisplay<'a> {
+    hash: &'a Hash,
+}
+
+impl fmt::Display for PhcDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phc = self.hash.to_phc_string().map_err(|_| fmt::Error)?;
+        f.write_str(&phc)
     }
 }
 
@@ -453,9 +3878,14 @@ impl FromStr for HashAlgorithm {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let algorithm = match s {
+        let algorithm = match normalize_algorithm(s).as_str() {
+            #[cfg(feature = "algo-argon2")]
             "argon2i" => HashAlgorithm::Argon2i,
+            #[cfg(feature = "algo-argon2")]
+            "argon2id" => HashAlgorithm::Argon2id,
+            #[cfg(feature = "algo-bcrypt")]
             "bcrypt" => HashAlgorithm::Bcrypt,
+            #[cfg(feature = "algo-scrypt")]
             "scrypt" => HashAlgorithm::Scrypt,
             _ => return Err(String::from("Invalid hash algorithm")),
         };
@@ -484,6 +3914,10 @@ pub struct HashBuilder {
     salt: Option<Salt>,
     /// The hash algorithm used.
     algorithm: Option<HashAlgorithm>,
+    /// The algorithm parameters used.
+    params: Option<Params>,
+    /// Where the salt came from.
+    salt_source: Option<SaltSource>,
 }
 
 impl HashBuilder {
@@ -493,6 +3927,8 @@ impl HashBuilder {
             hash: None,
             salt: None,
             algorithm: None,
+            params: None,
+            salt_source: None,
         }
     }
 
@@ -517,9 +3953,24 @@ impl HashBuilder {
         self
     }
 
+    /// Sets the `params` field in the builder.
+    /// The `self` parameter is consumed and returned to allow for method chaining.
+    pub fn params(mut self, params: Params) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Sets the `salt_source` field in the builder.
+    /// The `self` parameter is consumed and returned to allow for method chaining.
+    pub fn salt_source(mut self, salt_source: SaltSource) -> Self {
+        self.salt_source = Some(salt_source);
+        self
+    }
+
     /// Consumes the builder and returns a `Hash` if all fields are set.
+    /// The `params` and `salt_source` fields are optional.
     /// Otherwise, it returns an error.
-    pub fn build(self) -> Result<Hash, String> {
+    pub fn build(self) -> Result<Hash, HshError> {
         if let (Some(hash), Some(salt), Some(algorithm)) =
             (self.hash, self.salt, self.algorithm)
         {
@@ -527,9 +3978,12 @@ impl HashBuilder {
                 hash,
                 salt,
                 algorithm,
+                params: self.params,
+                original_phc: None,
+                salt_source: self.salt_source.unwrap_or_default(),
             })
         } else {
-            Err("Missing fields".to_string())
+            Err(HshError::MissingFields)
         }
     }
 }
@@ -540,3 +3994,20 @@ impl Default for HashBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_with_heartbeat_reports_panics() {
+        let result: Result<bool, HshError> =
+            run_with_heartbeat(|| panic!("backend failure"), || ());
+        assert_eq!(
+            result,
+            Err(HshError::Backend(String::from(
+                "verification thread panicked"
+            )))
+        );
+    }
+}
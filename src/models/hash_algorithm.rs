@@ -1,25 +1,33 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use serde::{Deserialize, Serialize};
+use super::error::HshError;
+use serde::{
+    de::{self, EnumAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
 
 /// Represents the different algorithms available for password hashing.
 ///
 /// This enum is used to specify which hashing algorithm should be used
 /// when creating a new hashed password.
 ///
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    Eq,
-    Hash,
-    Ord,
-    PartialEq,
-    PartialOrd,
-    Serialize,
-    Deserialize,
-)]
+/// Each variant is only available when its `algo-*` feature is
+/// enabled. The discriminants are fixed, so that serialized indices do
+/// not depend on the features.
+///
+/// The discriminants are written to stored data, as serde variant
+/// indices and in the `binary` layout, so they must never change. A
+/// new algorithm is appended with the next free discriminant; existing
+/// ones are never renumbered or reused. This is checked at compile
+/// time.
+///
+/// The enum is `#[non_exhaustive]`, so that algorithms can be added
+/// without breaking downstream code: matches outside this crate need a
+/// wildcard arm.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum HashAlgorithm {
     /// Argon2i - A memory-hard password hashing algorithm.
     ///
@@ -31,7 +39,8 @@ pub enum HashAlgorithm {
     /// - Memory usage
     /// - Parallelism
     /// - Time cost
-    Argon2i,
+    #[cfg(feature = "algo-argon2")]
+    Argon2i = 0,
 
     /// Bcrypt - A widely used, computationally intensive password hashing algorithm.
     ///
@@ -39,14 +48,332 @@ pub enum HashAlgorithm {
     /// - Based on the Blowfish encryption cipher
     /// - Uses key stretching technique
     /// - Time-consuming and resource-intensive, which makes it resistant to cracking
-    Bcrypt,
+    #[cfg(feature = "algo-bcrypt")]
+    Bcrypt = 1,
 
     /// Scrypt - A memory-hard password hashing algorithm designed for resistance to brute-force attacks.
     ///
     /// Features:
     /// - Consumes a large amount of memory
     /// - Makes parallelized attacks difficult and costly
-    Scrypt,
+    #[cfg(feature = "algo-scrypt")]
+    Scrypt = 2,
+
+    /// Argon2id - The hybrid Argon2 variant recommended for password
+    /// hashing by RFC 9106 and OWASP.
+    ///
+    /// Combines the strengths of Argon2i and Argon2d:
+    /// - A data-independent first pass, resisting side-channel attacks
+    /// - Data-dependent later passes, resisting GPU-based attacks
+    ///
+    /// Takes the same parameters as Argon2i.
+    #[cfg(feature = "algo-argon2")]
+    Argon2id = 3,
+}
+
+// Fails to compile if a discriminant of `HashAlgorithm` changes, or if
+// a variant is added without its name in `VARIANTS`.
+const _: () = {
+    #[cfg(feature = "algo-argon2")]
+    assert!(HashAlgorithm::Argon2i as u8 == 0);
+    #[cfg(feature = "algo-bcrypt")]
+    assert!(HashAlgorithm::Bcrypt as u8 == 1);
+    #[cfg(feature = "algo-scrypt")]
+    assert!(HashAlgorithm::Scrypt as u8 == 2);
+    #[cfg(feature = "algo-argon2")]
+    assert!(HashAlgorithm::Argon2id as u8 == 3);
+    assert!(VARIANTS.len() == 4);
+};
+
+/// Represents how suitable an algorithm is for new password hashes.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub enum SecurityLevel {
+    /// Memory-hard and recommended for new hashes.
+    Recommended,
+
+    /// Safe with a sufficient cost, but not memory-hard.
+    Acceptable,
+
+    /// Kept only to verify old hashes; hashes made with it should be
+    /// migrated to another algorithm.
+    Deprecated,
+}
+
+/// Represents how an algorithm checks a password against a stored
+/// hash.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub enum VerifyMethod {
+    /// The digest is derived again from the password, salt and
+    /// parameters, and compared with the stored digest in constant
+    /// time.
+    Recompute,
+
+    /// The backend checks the password against the stored hash itself,
+    /// including the comparison.
+    Backend,
+}
+
+impl HashAlgorithm {
+    /// Returns how the algorithm verifies passwords.
+    ///
+    /// Argon2i, Argon2id and Scrypt are `Recompute`: `Hash::verify` derives the
+    /// digest and compares it in one place. Bcrypt is `Backend`, as its
+    /// stored hash embeds the salt and cost and is checked by the
+    /// `bcrypt` crate.
+    pub fn verify_method(&self) -> VerifyMethod {
+        match self {
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i => VerifyMethod::Recompute,
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => VerifyMethod::Backend,
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => VerifyMethod::Recompute,
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2id => VerifyMethod::Recompute,
+        }
+    }
+
+    /// Returns the shortest salt, in bytes, accepted for new hashes.
+    ///
+    /// Argon2i, Argon2id and Scrypt require 8 bytes, the minimum recommended by
+    /// the PHC string format (and required by the Argon2 backend).
+    /// Bcrypt generates its own 16-byte salt and ignores any salt it is
+    /// given, so it has no minimum.
+    pub fn min_salt_len(&self) -> usize {
+        match self {
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i => 8,
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => 0,
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => 8,
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2id => 8,
+        }
+    }
+
+    /// Returns the shortest password, in bytes, accepted for new
+    /// hashes: `MIN_PASSWORD_LEN` for every algorithm.
+    pub fn min_password_len(&self) -> usize {
+        crate::MIN_PASSWORD_LEN
+    }
+
+    /// Returns `true` if the stored hash embeds its salt, as Bcrypt's
+    /// `$2b$<cost>$<salt><digest>` string does.
+    ///
+    /// The salt of such hashes is not serialized separately; see
+    /// `Hash`.
+    pub fn embeds_salt(&self) -> bool {
+        match self {
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Returns how suitable the algorithm is for new password hashes.
+    ///
+    /// Argon2i, Argon2id and Scrypt are memory-hard and `Recommended`. Bcrypt is
+    /// `Acceptable`: its fixed 4 KiB state leaves it more exposed to
+    /// GPU attacks. None of the supported algorithms is `Deprecated`
+    /// as such; `Hash::security_level` also considers the parameters,
+    /// and reports low-cost Bcrypt hashes as `Deprecated`.
+    pub fn security_level(&self) -> SecurityLevel {
+        match self {
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i => SecurityLevel::Recommended,
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => SecurityLevel::Acceptable,
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => SecurityLevel::Recommended,
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2id => SecurityLevel::Recommended,
+        }
+    }
+
+    /// Returns the algorithm identified by the tag of a PHC or modular
+    /// crypt string, the segment after the leading `$`.
+    ///
+    /// Argon2 and Scrypt use their names (`argon2i`, `argon2id`,
+    /// `scrypt`), while
+    /// Bcrypt hashes carry a revision tag: `2a`, `2b`, `2x` or `2y`.
+    /// Tags are matched exactly, without case folding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::hash_algorithm::HashAlgorithm;
+    ///
+    /// assert_eq!(
+    ///     HashAlgorithm::from_phc_tag("2b").unwrap(),
+    ///     HashAlgorithm::Bcrypt
+    /// );
+    /// assert!(HashAlgorithm::from_phc_tag("bcrypt").is_err());
+    /// ```
+    pub fn from_phc_tag(tag: &str) -> Result<Self, HshError> {
+        match tag {
+            #[cfg(feature = "algo-argon2")]
+            "argon2i" => Ok(HashAlgorithm::Argon2i),
+            #[cfg(feature = "algo-argon2")]
+            "argon2id" => Ok(HashAlgorithm::Argon2id),
+            #[cfg(feature = "algo-bcrypt")]
+            "2a" | "2b" | "2x" | "2y" => Ok(HashAlgorithm::Bcrypt),
+            #[cfg(feature = "algo-scrypt")]
+            "scrypt" => Ok(HashAlgorithm::Scrypt),
+            _ => Err(HshError::UnsupportedAlgorithm(tag.to_string())),
+        }
+    }
+
+    /// Returns the variant name, as written by `Serialize`.
+    fn variant_name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i => "Argon2i",
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => "Bcrypt",
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => "Scrypt",
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2id => "Argon2id",
+        }
+    }
+}
+
+/// The variant names of `HashAlgorithm`, as written by `Serialize`.
+const VARIANTS: &[&str] = &["Argon2i", "Bcrypt", "Scrypt", "Argon2id"];
+
+/// Serializes a `HashAlgorithm` as a unit variant, with its fixed
+/// discriminant as the variant index.
+impl Serialize for HashAlgorithm {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit_variant(
+            "HashAlgorithm",
+            *self as u32,
+            self.variant_name(),
+        )
+    }
+}
+
+/// Deserializes a `HashAlgorithm` from the variant name (or index)
+/// written by `Serialize`.
+///
+/// Unknown tags, such as an algorithm added by a newer version of the
+/// library, are reported as `HshError::UnsupportedAlgorithm` naming the
+/// tag, rather than as a generic serde error.
+impl<'de> Deserialize<'de> for HashAlgorithm {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum(
+            "HashAlgorithm",
+            VARIANTS,
+            HashAlgorithmVisitor,
+        )
+    }
+}
+
+struct HashAlgorithmVisitor;
+
+impl<'de> Visitor<'de> for HashAlgorithmVisitor {
+    type Value = HashAlgorithm;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a hash algorithm")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(
+        self,
+        data: A,
+    ) -> Result<Self::Value, A::Error> {
+        let (Tag(algorithm), variant) = data.variant()?;
+        variant.unit_variant()?;
+        Ok(algorithm)
+    }
+}
+
+/// The variant identifier of a serialized `HashAlgorithm`.
+struct Tag(HashAlgorithm);
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(TagVisitor)
+    }
+}
+
+struct TagVisitor;
+
+impl TagVisitor {
+    fn unsupported<E: de::Error>(tag: &str) -> E {
+        E::custom(HshError::UnsupportedAlgorithm(tag.to_string()))
+    }
+}
+
+impl Visitor<'_> for TagVisitor {
+    type Value = Tag;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a hash algorithm name")
+    }
+
+    fn visit_u64<E: de::Error>(self, index: u64) -> Result<Tag, E> {
+        match index {
+            #[cfg(feature = "algo-argon2")]
+            0 => Ok(Tag(HashAlgorithm::Argon2i)),
+            #[cfg(feature = "algo-bcrypt")]
+            1 => Ok(Tag(HashAlgorithm::Bcrypt)),
+            #[cfg(feature = "algo-scrypt")]
+            2 => Ok(Tag(HashAlgorithm::Scrypt)),
+            #[cfg(feature = "algo-argon2")]
+            3 => Ok(Tag(HashAlgorithm::Argon2id)),
+            _ => Err(Self::unsupported(&index.to_string())),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, tag: &str) -> Result<Tag, E> {
+        match tag {
+            #[cfg(feature = "algo-argon2")]
+            "Argon2i" => Ok(Tag(HashAlgorithm::Argon2i)),
+            #[cfg(feature = "algo-bcrypt")]
+            "Bcrypt" => Ok(Tag(HashAlgorithm::Bcrypt)),
+            #[cfg(feature = "algo-scrypt")]
+            "Scrypt" => Ok(Tag(HashAlgorithm::Scrypt)),
+            #[cfg(feature = "algo-argon2")]
+            "Argon2id" => Ok(Tag(HashAlgorithm::Argon2id)),
+            _ => Err(Self::unsupported(tag)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, tag: &[u8]) -> Result<Tag, E> {
+        self.visit_str(&String::from_utf8_lossy(tag))
+    }
 }
 
 /// Represents a generic hashing algorithm.
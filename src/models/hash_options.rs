@@ -0,0 +1,324 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{
+    error::HshError, hash_algorithm::HashAlgorithm, params::Params,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// The size of the Bcrypt state (four 256-entry S-boxes and an
+/// 18-entry P-array of 32-bit words), in bytes.
+#[cfg(feature = "algo-bcrypt")]
+const BCRYPT_STATE_BYTES: u64 = 4 * 256 * 4 + 18 * 4;
+
+/// The peak memory, in bytes, from which an Argon2 or Scrypt hash is
+/// considered expensive (16 MiB).
+pub const EXPENSIVE_MEMORY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// The Bcrypt cost from which a hash is considered expensive.
+pub const EXPENSIVE_BCRYPT_COST: u32 = 12;
+
+/// The smallest Argon2 memory cost, in KiB, recommended by
+/// `HashOptions::recommended_for_memory` (19 MiB, the OWASP minimum for
+/// Argon2).
+#[cfg(feature = "algo-argon2")]
+pub const RECOMMENDED_MIN_M_COST: u32 = 19 * 1024;
+
+/// The largest Argon2 memory cost, in KiB, recommended by
+/// `HashOptions::recommended_for_memory`. This is `MAX_MEMORY_KIB`, so
+/// that the recommendation always passes `Params::check_limits`.
+#[cfg(feature = "algo-argon2")]
+pub const RECOMMENDED_MAX_M_COST: u32 =
+    super::params::MAX_MEMORY_KIB as u32;
+
+/// The share of available memory, in percent, that a single Argon2
+/// hash is allowed to use.
+#[cfg(feature = "algo-argon2")]
+const RECOMMENDED_MEMORY_PERCENT: u64 = 5;
+
+/// The options set with `set_global_defaults`.
+static GLOBAL_DEFAULTS: OnceLock<HashOptions> = OnceLock::new();
+
+/// The algorithm and parameters of an existing hash, without its salt
+/// or digest.
+///
+/// Returned by `Hash::scheme`, so that policy code can inspect how a
+/// hash was made without handling any secret bytes.
+pub type Scheme = HashOptions;
+
+/// Represents the choice of algorithm and parameters used to create a
+/// hash.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct HashOptions {
+    /// The hash algorithm to use.
+    pub algorithm: HashAlgorithm,
+    /// The parameters to use, which must belong to `algorithm`.
+    pub params: Params,
+}
+
+impl HashOptions {
+    /// Creates options for the given algorithm and parameters.
+    ///
+    /// Returns `HshError::InvalidParams` if the parameters do not
+    /// belong to the algorithm or fail `Params::check_minimums`.
+    pub fn new(
+        algorithm: HashAlgorithm,
+        params: Params,
+    ) -> Result<Self, HshError> {
+        if !params.matches(algorithm) {
+            return Err(HshError::InvalidParams(format!(
+                "parameters do not belong to {}",
+                algorithm
+            )));
+        }
+        params.check_minimums()?;
+        Ok(HashOptions { algorithm, params })
+    }
+
+    /// Returns the options used by the constructors of this library
+    /// for the given algorithm.
+    pub fn default_for(algorithm: HashAlgorithm) -> Self {
+        HashOptions {
+            algorithm,
+            params: Params::default_for(algorithm),
+        }
+    }
+
+    /// Returns Argon2i options with a memory cost sized for a machine
+    /// with `available_bytes` of free memory.
+    ///
+    /// The heuristic gives a single hash 5% of the available memory,
+    /// then clamps the result between `RECOMMENDED_MIN_M_COST`
+    /// (19 MiB) and `RECOMMENDED_MAX_M_COST` (256 MiB). The time cost
+    /// is 3 and the parallelism 1, as in the library defaults, and new
+    /// hashes use Argon2 version 0x13.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash_options::HashOptions, params::Params};
+    ///
+    /// // 5% of 1 GiB is 51.2 MiB.
+    /// let options = HashOptions::recommended_for_memory(1 << 30);
+    /// match options.params {
+    ///     Params::Argon2 { m_cost, .. } => assert_eq!(m_cost, 52_428),
+    ///     other => panic!("unexpected parameters: {:?}", other),
+    /// }
+    /// ```
+    #[cfg(feature = "algo-argon2")]
+    pub fn recommended_for_memory(available_bytes: u64) -> Self {
+        let kib =
+            available_bytes / 100 * RECOMMENDED_MEMORY_PERCENT / 1024;
+        let m_cost = u32::try_from(kib)
+            .unwrap_or(u32::MAX)
+            .clamp(RECOMMENDED_MIN_M_COST, RECOMMENDED_MAX_M_COST);
+        let params = match Params::default_for(HashAlgorithm::Argon2i) {
+            Params::Argon2 {
+                version,
+                t_cost,
+                p_cost,
+                ..
+            } => Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            },
+            #[allow(unreachable_patterns)]
+            params => params,
+        };
+        HashOptions {
+            algorithm: HashAlgorithm::Argon2i,
+            params,
+        }
+    }
+
+    /// Returns Argon2i options sized from the memory currently
+    /// available on this machine, as reported by the operating system.
+    ///
+    /// See `recommended_for_memory` for the heuristic. Call this once
+    /// at startup rather than per hash, as querying the system is
+    /// comparatively slow.
+    #[cfg(feature = "sysinfo")]
+    pub fn recommended_from_system() -> Self {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        Self::recommended_for_memory(system.available_memory())
+    }
+
+    /// Returns the theoretical peak memory, in bytes, that hashing a
+    /// password with these options allocates.
+    ///
+    /// - Argon2: one 1 KiB block per unit of memory cost, after
+    ///   rounding to a multiple of `4 * p_cost` blocks (at least
+    ///   `8 * p_cost`), as done by Argon2 itself.
+    /// - Scrypt: `128 * r * N` bytes for the ROMix table plus
+    ///   `128 * r * p` bytes for the block buffer, where `N` is
+    ///   `2^log_n`.
+    /// - Bcrypt: the fixed 4168 byte cipher state.
+    ///
+    /// The result saturates at `u64::MAX` for parameters too large to
+    /// ever be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash_algorithm::HashAlgorithm, hash_options::HashOptions};
+    ///
+    /// let options = HashOptions::default_for(HashAlgorithm::Argon2i);
+    /// assert_eq!(options.estimated_memory_bytes(), 4096 * 1024);
+    /// ```
+    pub fn estimated_memory_bytes(&self) -> u64 {
+        match self.params {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 { m_cost, p_cost, .. } => {
+                let lanes = u64::from(p_cost).max(1);
+                let blocks = u64::from(m_cost).max(8 * lanes);
+                (blocks - blocks % (4 * lanes)).saturating_mul(1024)
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { .. } => BCRYPT_STATE_BYTES,
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { log_n, r, p } => {
+                let block = 128 * u64::from(r);
+                1u64.checked_shl(u32::from(log_n)).map_or(
+                    u64::MAX,
+                    |n| {
+                        block.saturating_mul(n).saturating_add(
+                            block.saturating_mul(u64::from(p)),
+                        )
+                    },
+                )
+            }
+        }
+    }
+
+    /// Returns `true` if hashing or verifying with these options is
+    /// slow enough that an interactive caller, such as a CLI, should
+    /// show a spinner.
+    ///
+    /// Argon2 and Scrypt are expensive from `EXPENSIVE_MEMORY_BYTES` of
+    /// estimated memory, and Bcrypt from `EXPENSIVE_BCRYPT_COST`. The
+    /// backends do not report progress, so this is decided from the
+    /// parameters alone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash_algorithm::HashAlgorithm, hash_options::HashOptions};
+    ///
+    /// assert!(HashOptions::default_for(HashAlgorithm::Scrypt).is_expensive());
+    /// ```
+    pub fn is_expensive(&self) -> bool {
+        match self.params {
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } => cost >= EXPENSIVE_BCRYPT_COST,
+            #[allow(unreachable_patterns)]
+            _ => {
+                self.estimated_memory_bytes() >= EXPENSIVE_MEMORY_BYTES
+            }
+        }
+    }
+
+    /// Returns a rough score of how costly a guess against a hash made
+    /// with these options is, comparable across algorithms.
+    ///
+    /// The score is the base 2 logarithm of the memory touched by one
+    /// hash, in KiB, times the number of passes over it:
+    ///
+    /// - Argon2: the estimated memory times `t_cost`.
+    /// - Scrypt: the estimated memory times 2, as ROMix fills the
+    ///   table once and then reads it back once.
+    /// - Bcrypt: the 4168 byte cipher state times the `2^cost` key
+    ///   expansions, which is about `cost + 2`.
+    ///
+    /// This is a heuristic meant for dashboards and reports. It ignores
+    /// parallelism, hardware and implementation differences, and must
+    /// not be used to decide whether a configuration is secure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::{hash_algorithm::HashAlgorithm, hash_options::HashOptions};
+    ///
+    /// let bcrypt = HashOptions::default_for(HashAlgorithm::Bcrypt);
+    /// let scrypt = HashOptions::default_for(HashAlgorithm::Scrypt);
+    /// assert!(scrypt.relative_strength() > bcrypt.relative_strength());
+    /// ```
+    pub fn relative_strength(&self) -> f64 {
+        let kib = self.estimated_memory_bytes() as f64 / 1024.0;
+        match self.params {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 { t_cost, .. } => {
+                (kib * f64::from(t_cost.max(1))).log2()
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } => f64::from(cost) + kib.log2(),
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { .. } => (kib * 2.0).log2(),
+        }
+    }
+}
+
+/// Sets the options that `Hash::new` uses in place of the library
+/// defaults, for every hash created with `options.algorithm`. Hashes
+/// created with other algorithms keep the library defaults.
+///
+/// The options can be set only once per process, typically at
+/// application start-up before any hash is created; later calls return
+/// `HshError::InvalidParams` and leave the first options in place.
+/// Setting and reading the options is thread safe.
+///
+/// Returns `HshError::InvalidParams` as well if the parameters do not
+/// belong to the algorithm or exceed the limits of
+/// `Params::check_limits`.
+///
+/// # Example
+///
+/// ```
+/// use hsh::models::{
+///     hash::Hash,
+///     hash_algorithm::HashAlgorithm,
+///     hash_options::{set_global_defaults, HashOptions},
+///     params::Params,
+/// };
+///
+/// let options = HashOptions::new(
+///     HashAlgorithm::Scrypt,
+///     Params::Scrypt { log_n: 10, r: 8, p: 1 },
+/// )
+/// .unwrap();
+/// set_global_defaults(options).unwrap();
+///
+/// let hash = Hash::new("password123", "somesalt", "scrypt").unwrap();
+/// assert_eq!(hash.parameters(), options.params);
+/// ```
+pub fn set_global_defaults(
+    options: HashOptions,
+) -> Result<(), HshError> {
+    let options = HashOptions::new(options.algorithm, options.params)?;
+    options.params.check_limits()?;
+    GLOBAL_DEFAULTS.set(options).map_err(|_| {
+        HshError::InvalidParams(String::from(
+            "global defaults are already set",
+        ))
+    })
+}
+
+/// Returns the options set with `set_global_defaults`, or `None` if
+/// they were not set.
+pub fn global_defaults() -> Option<HashOptions> {
+    GLOBAL_DEFAULTS.get().copied()
+}
@@ -1,8 +1,44 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+/// The `assessment` module contains the `Assessment` struct.
+pub mod assessment;
+
+/// The `compromised_filter` module contains the `CompromisedFilter`
+/// struct.
+pub mod compromised_filter;
+
+/// The `error` module contains the `HshError` enum.
+pub mod error;
+
 /// The `data` module contains the structs.
 pub mod hash;
 
 /// The `hash_algorithm` module contains the `HashAlgorithm` enum.
 pub mod hash_algorithm;
+
+/// The `hash_options` module contains the `HashOptions` struct.
+pub mod hash_options;
+
+/// The `params` module contains the `Params` enum.
+pub mod params;
+
+/// The `password_policy` module contains the `PasswordPolicy` struct.
+pub mod password_policy;
+
+/// The `prehash` module contains the `Prehash` enum.
+pub mod prehash;
+
+/// The `salt_encoding` module contains the `SaltEncoding` enum.
+pub mod salt_encoding;
+
+/// The `salt_source` module contains the `SaltSource` enum.
+pub mod salt_source;
+
+/// The `stored_credential` module contains the `StoredCredential`
+/// struct.
+pub mod stored_credential;
+
+/// The `sqlx_types` module maps `Hash` to SQLx text columns.
+#[cfg(feature = "sqlx")]
+mod sqlx_types;
@@ -0,0 +1,321 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{error::HshError, hash_algorithm::HashAlgorithm};
+use serde::{Deserialize, Serialize};
+
+/// The original Argon2 version (`0x10`), written as `v=16` in PHC
+/// strings. Hashes created by earlier releases of this library use it.
+pub const ARGON2_VERSION_10: u32 = 0x10;
+
+/// The current Argon2 version (`0x13`), written as `v=19` in PHC
+/// strings. New hashes use it.
+pub const ARGON2_VERSION_13: u32 = 0x13;
+
+/// The largest amount of memory, in KiB, that a stored hash may ask
+/// `login_verify` to allocate (256 MiB).
+pub const MAX_MEMORY_KIB: u64 = 256 * 1024;
+
+/// The largest Argon2 time cost accepted by `login_verify`.
+pub const MAX_ARGON2_T_COST: u32 = 32;
+
+/// The largest Argon2 or Scrypt parallelism accepted by
+/// `login_verify`.
+pub const MAX_PARALLELISM: u32 = 16;
+
+/// The largest Bcrypt cost accepted by `login_verify`.
+pub const MAX_BCRYPT_COST: u32 = 16;
+
+/// Represents the cost parameters used by a password hashing
+/// algorithm.
+///
+/// Parameters are recorded alongside a hash so that verification can
+/// reproduce the exact computation, and so that they can be written to
+/// and read from PHC strings.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+// Without `algo-scrypt`, the Argon2 variant is much larger than the
+// Bcrypt one; the enum is small and `Copy`, so boxing would not help.
+#[allow(variant_size_differences)]
+pub enum Params {
+    /// Argon2 parameters.
+    #[cfg(feature = "algo-argon2")]
+    Argon2 {
+        /// The Argon2 version (`0x10` or `0x13`).
+        version: u32,
+        /// The memory cost, in KiB.
+        m_cost: u32,
+        /// The time cost, in passes over memory.
+        t_cost: u32,
+        /// The degree of parallelism, in lanes.
+        p_cost: u32,
+    },
+
+    /// Bcrypt parameters.
+    #[cfg(feature = "algo-bcrypt")]
+    Bcrypt {
+        /// The cost, as a base-2 logarithm of the number of rounds.
+        cost: u32,
+    },
+
+    /// Scrypt parameters.
+    #[cfg(feature = "algo-scrypt")]
+    Scrypt {
+        /// The CPU/memory cost, as a base-2 logarithm of `N`.
+        log_n: u8,
+        /// The block size.
+        r: u32,
+        /// The degree of parallelism.
+        p: u32,
+    },
+}
+
+impl Params {
+    /// Returns the parameters used by the constructors of this library.
+    ///
+    /// For Bcrypt, the cost is embedded in the hash itself, so the
+    /// library default cost is returned.
+    pub fn default_for(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            #[cfg(feature = "algo-argon2")]
+            HashAlgorithm::Argon2i | HashAlgorithm::Argon2id => {
+                Params::Argon2 {
+                    version: ARGON2_VERSION_13,
+                    m_cost: 4096,
+                    t_cost: 3,
+                    p_cost: 1,
+                }
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            HashAlgorithm::Bcrypt => Params::Bcrypt {
+                cost: bcrypt::DEFAULT_COST,
+            },
+            #[cfg(feature = "algo-scrypt")]
+            HashAlgorithm::Scrypt => Params::Scrypt {
+                log_n: 14,
+                r: 8,
+                p: 1,
+            },
+        }
+    }
+
+    /// Returns the parameters assumed for hashes stored without
+    /// parameters.
+    ///
+    /// Such hashes were created by earlier releases of this library,
+    /// which used Argon2 version `0x10`; the other parameters match
+    /// `default_for`. Argon2id was added later, so its legacy
+    /// parameters are its defaults.
+    pub fn legacy(algorithm: HashAlgorithm) -> Self {
+        match Params::default_for(algorithm) {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                m_cost,
+                t_cost,
+                p_cost,
+                ..
+            } if algorithm == HashAlgorithm::Argon2i => {
+                Params::Argon2 {
+                    version: ARGON2_VERSION_10,
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                }
+            }
+            #[allow(unreachable_patterns)]
+            params => params,
+        }
+    }
+
+    /// Returns `true` if these parameters belong to the given
+    /// algorithm.
+    pub fn matches(&self, algorithm: HashAlgorithm) -> bool {
+        match (self, algorithm) {
+            #[cfg(feature = "algo-argon2")]
+            (
+                Params::Argon2 { .. },
+                HashAlgorithm::Argon2i | HashAlgorithm::Argon2id,
+            ) => true,
+            #[cfg(feature = "algo-bcrypt")]
+            (Params::Bcrypt { .. }, HashAlgorithm::Bcrypt) => true,
+            #[cfg(feature = "algo-scrypt")]
+            (Params::Scrypt { .. }, HashAlgorithm::Scrypt) => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Checks that the parameters are large enough to be hashed with.
+    ///
+    /// Argon2 needs a time cost and a parallelism of at least 1, and a
+    /// memory cost of at least `8 * p_cost` KiB. Such parameters are
+    /// rejected when a hash is created, parsed or deserialized, before
+    /// they reach the backend.
+    pub fn check_minimums(&self) -> Result<(), HshError> {
+        match *self {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                m_cost,
+                t_cost,
+                p_cost,
+                ..
+            } => check_argon2_minimums(m_cost, t_cost, p_cost),
+            #[allow(unreachable_patterns)]
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the parameters are at least as strong as `min`,
+    /// returning `HshError::ParamsBelowPolicy` otherwise.
+    ///
+    /// Every cost is compared separately: Argon2 `m_cost`, `t_cost` and
+    /// `p_cost`, the Bcrypt `cost`, and Scrypt `log_n`, `r` and `p`.
+    /// The Argon2 version is not compared. Parameters of another
+    /// algorithm than `min` never meet it.
+    pub fn check_at_least(&self, min: &Params) -> Result<(), HshError> {
+        let below = match (*self, *min) {
+            #[cfg(feature = "algo-argon2")]
+            (
+                Params::Argon2 {
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                    ..
+                },
+                Params::Argon2 {
+                    m_cost: min_m,
+                    t_cost: min_t,
+                    p_cost: min_p,
+                    ..
+                },
+            ) => m_cost < min_m || t_cost < min_t || p_cost < min_p,
+            #[cfg(feature = "algo-bcrypt")]
+            (
+                Params::Bcrypt { cost },
+                Params::Bcrypt { cost: min_cost },
+            ) => cost < min_cost,
+            #[cfg(feature = "algo-scrypt")]
+            (
+                Params::Scrypt { log_n, r, p },
+                Params::Scrypt {
+                    log_n: min_log_n,
+                    r: min_r,
+                    p: min_p,
+                },
+            ) => log_n < min_log_n || r < min_r || p < min_p,
+            #[allow(unreachable_patterns)]
+            _ => true,
+        };
+        if below {
+            return Err(HshError::ParamsBelowPolicy(format!(
+                "{:?} do not meet the minimum {:?}",
+                self, min
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the parameters stay within the limits enforced by
+    /// `login_verify`, so that an untrusted stored hash cannot make a
+    /// single verification allocate excessive memory or run for an
+    /// excessive time.
+    pub fn check_limits(&self) -> Result<(), HshError> {
+        match *self {
+            #[cfg(feature = "algo-argon2")]
+            Params::Argon2 {
+                m_cost,
+                t_cost,
+                p_cost,
+                ..
+            } => {
+                if u64::from(m_cost) > MAX_MEMORY_KIB {
+                    return Err(HshError::InvalidParams(format!(
+                        "Argon2 memory cost {} KiB exceeds the limit of {} KiB",
+                        m_cost, MAX_MEMORY_KIB
+                    )));
+                }
+                if t_cost > MAX_ARGON2_T_COST {
+                    return Err(HshError::InvalidParams(format!(
+                        "Argon2 time cost {} exceeds the limit of {}",
+                        t_cost, MAX_ARGON2_T_COST
+                    )));
+                }
+                if p_cost > MAX_PARALLELISM {
+                    return Err(HshError::InvalidParams(format!(
+                        "Argon2 parallelism {} exceeds the limit of {}",
+                        p_cost, MAX_PARALLELISM
+                    )));
+                }
+            }
+            #[cfg(feature = "algo-bcrypt")]
+            Params::Bcrypt { cost } => {
+                if cost > MAX_BCRYPT_COST {
+                    return Err(HshError::InvalidParams(format!(
+                        "Bcrypt cost {} exceeds the limit of {}",
+                        cost, MAX_BCRYPT_COST
+                    )));
+                }
+            }
+            #[cfg(feature = "algo-scrypt")]
+            Params::Scrypt { log_n, r, p } => {
+                // Scrypt uses 128 * r * 2^log_n bytes of memory.
+                let memory_kib = 1u64
+                    .checked_shl(u32::from(log_n))
+                    .and_then(|n| n.checked_mul(u64::from(r)))
+                    .map(|n| n / 8);
+                if memory_kib.map_or(true, |kib| kib > MAX_MEMORY_KIB) {
+                    return Err(HshError::InvalidParams(format!(
+                        "Scrypt memory cost (log_n={}, r={}) exceeds the limit of {} KiB",
+                        log_n, r, MAX_MEMORY_KIB
+                    )));
+                }
+                if p > MAX_PARALLELISM {
+                    return Err(HshError::InvalidParams(format!(
+                        "Scrypt parallelism {} exceeds the limit of {}",
+                        p, MAX_PARALLELISM
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the Argon2 minimums described in `Params::check_minimums`.
+#[cfg(feature = "algo-argon2")]
+pub(crate) fn check_argon2_minimums(
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<(), HshError> {
+    if t_cost < 1 {
+        return Err(HshError::InvalidParams(String::from(
+            "Argon2 time cost must be at least 1",
+        )));
+    }
+    if p_cost < 1 {
+        return Err(HshError::InvalidParams(String::from(
+            "Argon2 parallelism must be at least 1",
+        )));
+    }
+    if u64::from(m_cost) < 8 * u64::from(p_cost) {
+        return Err(HshError::InvalidParams(format!(
+            "Argon2 memory cost {} KiB is below the minimum of {} KiB: \
+             each of the {} lanes needs at least 8 KiB",
+            m_cost,
+            8 * u64::from(p_cost),
+            p_cost
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::error::HshError;
+use serde::{Deserialize, Serialize};
+
+/// Represents the password lengths accepted when creating a hash.
+///
+/// Lengths are counted in bytes. The default policy accepts passwords
+/// of at least `MIN_PASSWORD_LEN` bytes, with no maximum, and is the
+/// one used by `Hash::new`; `Hash::new_with_policy` takes another.
+///
+/// # Example
+///
+/// ```
+/// use hsh::models::{error::HshError, password_policy::PasswordPolicy};
+///
+/// let policy = PasswordPolicy::new(12).with_max_len(64);
+/// assert!(policy.check(b"correct horse battery").is_ok());
+/// assert_eq!(
+///     policy.check(b"short"),
+///     Err(HshError::PasswordTooShort { min: 12 })
+/// );
+/// ```
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct PasswordPolicy {
+    /// The shortest accepted password, in bytes.
+    pub min_len: usize,
+    /// The longest accepted password, in bytes, if any.
+    pub max_len: Option<usize>,
+}
+
+impl PasswordPolicy {
+    /// Creates a policy accepting passwords of at least `min_len`
+    /// bytes, with no maximum.
+    pub fn new(min_len: usize) -> Self {
+        PasswordPolicy {
+            min_len,
+            max_len: None,
+        }
+    }
+
+    /// Returns the policy with its longest accepted password set to
+    /// `max_len` bytes.
+    pub fn with_max_len(self, max_len: usize) -> Self {
+        PasswordPolicy {
+            max_len: Some(max_len),
+            ..self
+        }
+    }
+
+    /// Checks the length of a password against the policy.
+    ///
+    /// Returns `HshError::PasswordTooShort` or
+    /// `HshError::PasswordTooLong`, reporting the policy's limit.
+    pub fn check(&self, password: &[u8]) -> Result<(), HshError> {
+        if password.len() < self.min_len {
+            return Err(HshError::PasswordTooShort {
+                min: self.min_len,
+            });
+        }
+        if let Some(max) = self.max_len {
+            if password.len() > max {
+                return Err(HshError::PasswordTooLong { max });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        PasswordPolicy::new(crate::MIN_PASSWORD_LEN)
+    }
+}
+
+impl From<usize> for PasswordPolicy {
+    /// Creates a policy with the given minimum length, as
+    /// `PasswordPolicy::new` does.
+    fn from(min_len: usize) -> Self {
+        PasswordPolicy::new(min_len)
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::error::HshError;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Represents a transform applied to a password before it was hashed.
+///
+/// Some systems pepper passwords by hashing `HMAC(pepper, password)`
+/// instead of the password itself. To verify their hashes, the same
+/// transform has to be applied first; see `Hash::verify_prehashed`.
+///
+/// The digest of a transform is passed on as its lowercase hex
+/// encoding (64 characters for SHA-256), which is the form such
+/// systems commonly feed to the password hash.
+#[derive(Clone, Eq, PartialEq)]
+pub enum Prehash {
+    /// The password is used as is.
+    None,
+
+    /// The password is replaced by `HMAC-SHA256(key, password)`.
+    HmacSha256 {
+        /// The HMAC key, usually an application-wide pepper.
+        key: Vec<u8>,
+    },
+
+    /// The password is replaced by `SHA-256(password)`.
+    Sha256,
+}
+
+impl Prehash {
+    /// Applies the transform to a password, giving the string that was
+    /// hashed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::prehash::Prehash;
+    ///
+    /// assert_eq!(
+    ///     Prehash::Sha256.apply("abc").unwrap(),
+    ///     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    /// );
+    /// ```
+    pub fn apply(&self, password: &str) -> Result<String, HshError> {
+        match self {
+            Prehash::None => Ok(password.to_string()),
+            Prehash::HmacSha256 { key } => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key)
+                    .map_err(|e| {
+                        HshError::InvalidParams(e.to_string())
+                    })?;
+                mac.update(password.as_bytes());
+                Ok(encode_hex(&mac.finalize().into_bytes()))
+            }
+            Prehash::Sha256 => {
+                Ok(encode_hex(&Sha256::digest(password.as_bytes())))
+            }
+        }
+    }
+}
+
+/// Formats the transform without revealing the HMAC key.
+impl fmt::Debug for Prehash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Prehash::None => f.write_str("None"),
+            Prehash::HmacSha256 { .. } => f
+                .debug_struct("HmacSha256")
+                .field("key", &"<redacted>")
+                .finish(),
+            Prehash::Sha256 => f.write_str("Sha256"),
+        }
+    }
+}
+
+/// Encodes bytes as lowercase hex.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,94 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{error::HshError, hash::Salt};
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine as _,
+};
+use serde::{Deserialize, Serialize};
+
+/// The base64 engine used for encoded salts: standard alphabet, with
+/// or without padding.
+const SALT_B64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Represents the encoding a salt may have been stored in.
+///
+/// Used to re-interpret a stored salt, for example when a hash was
+/// created from a hex encoded salt string but the salt is now expected
+/// as raw bytes.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub enum SaltEncoding {
+    /// The stored bytes are the salt itself.
+    Raw,
+
+    /// The stored bytes are the hex encoding of the salt.
+    Hex,
+
+    /// The stored bytes are the base64 encoding of the salt, with or
+    /// without padding.
+    Base64,
+}
+
+impl SaltEncoding {
+    /// Decodes a stored salt under this encoding, giving the raw salt
+    /// bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hsh::models::salt_encoding::SaltEncoding;
+    ///
+    /// let salt = SaltEncoding::Hex.decode(b"736f6d6573616c74").unwrap();
+    /// assert_eq!(salt, b"somesalt");
+    /// ```
+    pub fn decode(&self, stored: &[u8]) -> Result<Salt, HshError> {
+        match self {
+            SaltEncoding::Raw => Ok(stored.to_vec()),
+            SaltEncoding::Hex => decode_hex(stored),
+            SaltEncoding::Base64 => {
+                SALT_B64.decode(stored).map_err(|_| {
+                    HshError::CorruptStoredHash(String::from(
+                        "salt is not valid base64",
+                    ))
+                })
+            }
+        }
+    }
+}
+
+/// Decodes a hex string, in upper or lower case.
+fn decode_hex(stored: &[u8]) -> Result<Salt, HshError> {
+    let invalid = || {
+        HshError::CorruptStoredHash(String::from(
+            "salt is not valid hex",
+        ))
+    };
+    if stored.len() % 2 != 0 {
+        return Err(invalid());
+    }
+    stored
+        .chunks(2)
+        .map(|pair| {
+            let digits =
+                std::str::from_utf8(pair).map_err(|_| invalid())?;
+            u8::from_str_radix(digits, 16).map_err(|_| invalid())
+        })
+        .collect()
+}
@@ -0,0 +1,58 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use serde::{Deserialize, Serialize};
+
+/// Represents where the salt of a `Hash` came from.
+///
+/// This is a debugging aid, for example to find out why two hashes of
+/// the same password differ. It is recorded when a hash is created, is
+/// never serialized, and is ignored when hashes are compared; parsed
+/// and deserialized hashes report `Unknown`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub enum SaltSource {
+    /// The source was not recorded, as for hashes parsed from a
+    /// string or built with `HashBuilder`.
+    #[default]
+    Unknown,
+
+    /// The caller supplied the salt.
+    Provided,
+
+    /// The salt was generated with the given random number generator.
+    Generated(SaltRng),
+}
+
+/// Represents the random number generator a salt was generated with.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub enum SaltRng {
+    /// The operating system's CSPRNG, as used by `Hash::generate_salt`.
+    OsRng,
+
+    /// The algorithm backend generated its own salt. Bcrypt does this
+    /// for every new hash, ignoring any salt it is given.
+    Backend,
+}
@@ -0,0 +1,49 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! SQLx integration for `Hash`.
+//!
+//! A `Hash` is stored in a text column as its PHC string, so a struct
+//! deriving `sqlx::FromRow` can hold a `Hash` field directly.
+
+use super::hash::Hash;
+use ::sqlx::{
+    encode::IsNull, error::BoxDynError, Database, Decode, Encode, Type,
+};
+
+impl<DB: Database> Type<DB> for Hash
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Hash
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.to_phc_string()?.encode(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Hash
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(
+        value: <DB as Database>::ValueRef<'r>,
+    ) -> Result<Self, BoxDynError> {
+        let phc = <String as Decode<'r, DB>>::decode(value)?;
+        Ok(Hash::parse_phc(&phc)?)
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{
+    hash::Hash, hash_algorithm::HashAlgorithm, params::Params,
+};
+use serde::{Deserialize, Serialize};
+
+/// Represents a password hash stored as separate columns: algorithm,
+/// parameters, salt and digest.
+///
+/// This is the columnar counterpart to a PHC string, for applications
+/// that keep each component in its own column. It is checked with
+/// `Hash::verify_stored`, which uses the components exactly as stored;
+/// see `Hash::verify_components`.
+///
+/// # Example
+///
+/// ```
+/// use hsh::models::{hash::Hash, stored_credential::StoredCredential};
+///
+/// let hash = Hash::new("password123", "somesalt", "argon2i").unwrap();
+/// let credential = StoredCredential::from(&hash);
+///
+/// assert_eq!(Hash::verify_stored(&credential, "password123"), Ok(true));
+/// ```
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct StoredCredential {
+    /// The hash algorithm.
+    pub algorithm: HashAlgorithm,
+    /// The parameters the digest was derived with.
+    pub params: Params,
+    /// The salt. Bcrypt embeds its salt in the digest and ignores it.
+    pub salt: Vec<u8>,
+    /// The digest; for Bcrypt, the whole `$2b$...` string.
+    pub hash: Vec<u8>,
+}
+
+impl From<&Hash> for StoredCredential {
+    /// Splits a hash into its components, with the parameters reported
+    /// by `Hash::parameters`.
+    fn from(hash: &Hash) -> Self {
+        StoredCredential {
+            algorithm: hash.algorithm,
+            params: hash.parameters(),
+            salt: hash.salt.clone(),
+            hash: hash.hash.clone(),
+        }
+    }
+}
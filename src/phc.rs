@@ -0,0 +1,302 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Parsing and encoding of PHC strings.
+//!
+//! The following layouts are understood:
+//!
+//! - Argon2i: `$argon2i$v=19$m=4096,t=3,p=1$<salt>$<hash>`, where the
+//!   version is `19` (`0x13`) for new hashes and `16` (`0x10`) for
+//!   hashes created by earlier releases
+//! - Argon2id: `$argon2id$v=19$m=4096,t=3,p=1$<salt>$<hash>`
+//! - Scrypt: `$scrypt$ln=14,r=8,p=1$<salt>$<hash>`
+//! - Bcrypt: `$2b$12$<salt and hash>`, also with the `2a`, `2x` and
+//!   `2y` prefixes
+//!
+//! Parameters may appear in any order, but each exactly once. The salt
+//! and hash segments are base64 encoded with the standard alphabet,
+//! with or without padding, and must not be empty.
+//!
+//! Input containing ASCII control characters, such as an injected
+//! newline or NUL byte, is rejected outright. The parser never panics:
+//! any input that does not follow one of the layouts above is reported
+//! as an `HshError`.
+
+use crate::models::{
+    error::HshError, hash::Hash, hash_algorithm::HashAlgorithm,
+    params::Params, salt_source::SaltSource,
+};
+#[cfg(any(feature = "algo-argon2", feature = "algo-scrypt"))]
+use base64::display::Base64Display;
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine as _,
+};
+use std::io::Write;
+
+/// The base64 engine used by PHC strings: standard alphabet, written
+/// without padding, and read with or without it.
+pub(crate) const PHC_B64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// The base64 engine used by the salt in Bcrypt hashes: the Bcrypt
+/// alphabet, without padding. The last of the 22 salt characters
+/// carries only two bits, so any trailing bits are accepted.
+#[cfg(feature = "algo-bcrypt")]
+pub(crate) const BCRYPT_B64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::BCRYPT,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::RequireNone)
+        .with_decode_allow_trailing_bits(true),
+);
+
+/// The number of base64 characters taken by the salt in a Bcrypt hash.
+#[cfg(feature = "algo-bcrypt")]
+const BCRYPT_SALT_B64_LEN: usize = 22;
+
+/// Parses a PHC string into a `Hash`.
+///
+/// The decoded salt and the parameters are stored on the returned
+/// `Hash` so that it can be verified directly.
+///
+/// # Example
+///
+/// ```
+/// use hsh::{models::hash_algorithm::HashAlgorithm, phc};
+///
+/// let hash = phc::parse(
+///     "$scrypt$ln=10,r=8,p=1$c29tZXNhbHQ$AgGjc1uwpRUm7ZMofHPj/fmCk9FDtbjURXhD+9G5788",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(hash.algorithm(), HashAlgorithm::Scrypt);
+/// assert_eq!(hash.salt(), b"somesalt");
+/// ```
+pub fn parse(hash_str: &str) -> Result<Hash, HshError> {
+    if hash_str.bytes().any(|b| b.is_ascii_control()) {
+        return Err(HshError::InvalidHashString);
+    }
+
+    let parts: Vec<&str> = hash_str.split('$').collect();
+    if parts.len() < 4 || !parts[0].is_empty() {
+        return Err(HshError::InvalidHashString);
+    }
+
+    match HashAlgorithm::from_phc_tag(parts[1])? {
+        #[cfg(feature = "algo-argon2")]
+        algorithm @ (HashAlgorithm::Argon2i
+        | HashAlgorithm::Argon2id) => {
+            // The version segment is required, as the digest depends
+            // on it.
+            if parts.len() != 6 {
+                return Err(HshError::InvalidHashString);
+            }
+            let version = parts[2]
+                .strip_prefix("v=")
+                .ok_or(HshError::InvalidHashString)
+                .and_then(parse_decimal)?;
+            let values = parse_params(parts[3], &["m", "t", "p"])?;
+            let params = Params::Argon2 {
+                version,
+                m_cost: values[0],
+                t_cost: values[1],
+                p_cost: values[2],
+            };
+            params.check_minimums()?;
+            Ok(Hash {
+                hash: decode_b64(parts[5])?,
+                salt: decode_b64(parts[4])?,
+                algorithm,
+                params: Some(params),
+                original_phc: None,
+                salt_source: SaltSource::Unknown,
+            })
+        }
+        #[cfg(feature = "algo-scrypt")]
+        HashAlgorithm::Scrypt => {
+            if parts.len() != 5 {
+                return Err(HshError::InvalidHashString);
+            }
+            let values = parse_params(parts[2], &["ln", "r", "p"])?;
+            let log_n = u8::try_from(values[0]).map_err(|_| {
+                HshError::InvalidParams(format!(
+                    "Scrypt log_n {} is out of range",
+                    values[0]
+                ))
+            })?;
+            Ok(Hash {
+                hash: decode_b64(parts[4])?,
+                salt: decode_b64(parts[3])?,
+                algorithm: HashAlgorithm::Scrypt,
+                params: Some(Params::Scrypt {
+                    log_n,
+                    r: values[1],
+                    p: values[2],
+                }),
+                original_phc: None,
+                salt_source: SaltSource::Unknown,
+            })
+        }
+        #[cfg(feature = "algo-bcrypt")]
+        HashAlgorithm::Bcrypt => {
+            // Bcrypt embeds a 22 character salt and a 31 character
+            // hash in the last segment.
+            if parts.len() != 4
+                || parts[2].len() != 2
+                || parts[3].len() != 53
+            {
+                return Err(HshError::InvalidHashString);
+            }
+            let cost = parse_decimal(parts[2])?;
+            Ok(Hash {
+                hash: hash_str.as_bytes().to_vec(),
+                salt: decode_bcrypt_salt(parts[3])?,
+                algorithm: HashAlgorithm::Bcrypt,
+                params: Some(Params::Bcrypt { cost }),
+                original_phc: None,
+                salt_source: SaltSource::Unknown,
+            })
+        }
+    }
+}
+
+/// Encodes a `Hash` as a PHC string.
+///
+/// Argon2 and Scrypt hashes are written with their parameters, salt
+/// and digest, in the layout read by `parse`. Bcrypt hashes are
+/// already stored in their modular crypt form (`$2b$...`), which is
+/// returned as is.
+pub fn encode(hash: &Hash) -> Result<String, HshError> {
+    let mut buf = Vec::new();
+    write(hash, &mut buf)?;
+    String::from_utf8(buf).map_err(|_| {
+        HshError::CorruptStoredHash(String::from(
+            "PHC string is not valid UTF-8",
+        ))
+    })
+}
+
+/// Writes the PHC string of a `Hash` to a writer, without building an
+/// intermediate `String`.
+///
+/// The output is identical to `encode`.
+pub fn write<W: Write>(hash: &Hash, w: &mut W) -> Result<(), HshError> {
+    match hash.stored_params()? {
+        #[cfg(feature = "algo-argon2")]
+        Params::Argon2 {
+            version,
+            m_cost,
+            t_cost,
+            p_cost,
+        } => write!(
+            w,
+            "${}$v={}$m={},t={},p={}${}${}",
+            argon2_tag(hash.algorithm),
+            version,
+            m_cost,
+            t_cost,
+            p_cost,
+            Base64Display::new(&hash.salt, &PHC_B64),
+            Base64Display::new(&hash.hash, &PHC_B64)
+        ),
+        #[cfg(feature = "algo-bcrypt")]
+        Params::Bcrypt { .. } => {
+            let mcf =
+                std::str::from_utf8(&hash.hash).map_err(|_| {
+                    HshError::CorruptStoredHash(String::from(
+                        "Bcrypt hash is not valid UTF-8",
+                    ))
+                })?;
+            w.write_all(mcf.as_bytes())
+        }
+        #[cfg(feature = "algo-scrypt")]
+        Params::Scrypt { log_n, r, p } => write!(
+            w,
+            "$scrypt$ln={},r={},p={}${}${}",
+            log_n,
+            r,
+            p,
+            Base64Display::new(&hash.salt, &PHC_B64),
+            Base64Display::new(&hash.hash, &PHC_B64)
+        ),
+    }
+    .map_err(|e| HshError::Io(e.to_string()))
+}
+
+/// Returns the PHC tag of an Argon2 variant.
+#[cfg(feature = "algo-argon2")]
+fn argon2_tag(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Argon2id => "argon2id",
+        _ => "argon2i",
+    }
+}
+
+/// Parses a decimal PHC value, rejecting signs and empty strings.
+fn parse_decimal(value: &str) -> Result<u32, HshError> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(HshError::InvalidHashString);
+    }
+    value.parse().map_err(|_| {
+        HshError::InvalidParams(format!("{} is out of range", value))
+    })
+}
+
+/// Parses a PHC parameter segment such as `m=4096,t=3,p=1`.
+///
+/// Every key in `keys` must appear exactly once, in any order, and no
+/// other key is allowed. The values are returned in the order of
+/// `keys`.
+#[cfg(any(feature = "algo-argon2", feature = "algo-scrypt"))]
+fn parse_params(
+    segment: &str,
+    keys: &[&str],
+) -> Result<Vec<u32>, HshError> {
+    let mut values: Vec<Option<u32>> = vec![None; keys.len()];
+    for pair in segment.split(',') {
+        let (key, value) =
+            pair.split_once('=').ok_or(HshError::InvalidHashString)?;
+        let index = keys
+            .iter()
+            .position(|k| *k == key)
+            .ok_or(HshError::InvalidHashString)?;
+        if values[index].replace(parse_decimal(value)?).is_some() {
+            return Err(HshError::InvalidHashString);
+        }
+    }
+    values
+        .into_iter()
+        .collect::<Option<Vec<u32>>>()
+        .ok_or(HshError::InvalidHashString)
+}
+
+/// Decodes a non-empty base64 segment of a PHC string.
+#[cfg(any(feature = "algo-argon2", feature = "algo-scrypt"))]
+fn decode_b64(segment: &str) -> Result<Vec<u8>, HshError> {
+    if segment.is_empty() {
+        return Err(HshError::InvalidHashString);
+    }
+    PHC_B64
+        .decode(segment)
+        .map_err(|_| HshError::InvalidBase64(segment.to_string()))
+}
+
+/// Decodes the salt from the salt-and-hash segment of a Bcrypt hash,
+/// giving the 16 raw salt bytes.
+#[cfg(feature = "algo-bcrypt")]
+pub(crate) fn decode_bcrypt_salt(
+    segment: &str,
+) -> Result<crate::models::hash::Salt, HshError> {
+    let encoded = segment
+        .get(..BCRYPT_SALT_B64_LEN)
+        .ok_or(HshError::InvalidHashString)?;
+    BCRYPT_B64
+        .decode(encoded)
+        .map_err(|_| HshError::InvalidBase64(encoded.to_string()))
+}
@@ -0,0 +1,39 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for a build with only Argon2i compiled in, run with
+//! `--no-default-features --features algo-argon2`.
+
+#[cfg(all(
+    test,
+    feature = "algo-argon2",
+    not(feature = "algo-bcrypt"),
+    not(feature = "algo-scrypt")
+))]
+mod tests {
+    use hsh::models::{
+        error::HshError, hash::Hash, hash_algorithm::HashAlgorithm,
+    };
+
+    #[test]
+    fn test_argon2i_hashes_and_verifies() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        assert_eq!(hash.algorithm, HashAlgorithm::Argon2i);
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(hash.verify("password456"), Ok(false));
+    }
+
+    #[test]
+    fn test_disabled_algorithms_are_unsupported() {
+        assert_eq!(
+            Hash::new("password123", "somesalt", "bcrypt"),
+            Err(HshError::UnsupportedAlgorithm(String::from("bcrypt")))
+        );
+        assert!(Hash::new("password123", "somesalt", "scrypt").is_err());
+        assert_eq!(
+            HashAlgorithm::from_phc_tag("2b"),
+            Err(HshError::UnsupportedAlgorithm(String::from("2b")))
+        );
+    }
+}
@@ -1,9 +1,12 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the Argon2i hashing algorithm.
+
 #[cfg(test)]
 mod tests {
     use hsh::algorithms::argon2i::Argon2i;
+    use hsh::models::error::HshError;
     use hsh::models::hash::Hash;
     use hsh::models::hash_algorithm::{
         HashAlgorithm, HashingAlgorithm,
@@ -87,4 +90,106 @@ mod tests {
         assert_eq!(hash.hash, hash_bytes);
         assert_eq!(hash.algorithm, HashAlgorithm::Argon2i);
     }
+
+    #[test]
+    fn test_construction_rejects_too_small_params() {
+        // (m_cost, t_cost, p_cost), each with one value too small.
+        for (m_cost, t_cost, p_cost) in
+            [(4096, 0, 1), (4096, 3, 0), (7, 3, 1), (31, 3, 4)]
+        {
+            let mut output = [0u8; 32];
+            let result = Argon2i::hash_with_params(
+                b"password123",
+                b"somesalt",
+                0x13,
+                m_cost,
+                t_cost,
+                p_cost,
+                &mut output,
+            );
+            assert!(
+                matches!(result, Err(HshError::InvalidParams(_))),
+                "m={} t={} p={}: {:?}",
+                m_cost,
+                t_cost,
+                p_cost,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_too_small_params() {
+        let stored = Hash::new("password123", "somesalt", "argon2i")
+            .unwrap()
+            .to_phc_string()
+            .unwrap();
+        assert!(stored.contains("m=4096,t=3,p=1"));
+
+        for params in
+            ["m=4096,t=0,p=1", "m=4096,t=3,p=0", "m=7,t=3,p=1"]
+        {
+            let tampered = stored.replace("m=4096,t=3,p=1", params);
+            assert!(
+                matches!(
+                    hsh::phc::parse(&tampered),
+                    Err(HshError::InvalidParams(_))
+                ),
+                "{}",
+                params
+            );
+        }
+    }
+
+    #[test]
+    fn test_memory_cost_must_cover_every_lane() {
+        // p=4 needs at least 8 * 4 = 32 KiB.
+        let result = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x13,
+            16,
+            1,
+            4,
+            32,
+        );
+        match result {
+            Err(HshError::InvalidParams(reason)) => {
+                assert!(
+                    reason.contains("minimum of 32 KiB"),
+                    "{}",
+                    reason
+                );
+                assert!(reason.contains("4 lanes"), "{}", reason);
+            }
+            other => panic!("expected InvalidParams, got {:?}", other),
+        }
+
+        let hash = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x13,
+            64,
+            1,
+            4,
+            32,
+        )
+        .unwrap();
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        // The parse and deserialization paths reject it too.
+        let stored = hash.to_phc_string().unwrap();
+        assert!(stored.contains("m=64,t=1,p=4"));
+        let tampered = stored.replace("m=64,", "m=16,");
+        assert!(matches!(
+            Hash::from_string(&tampered),
+            Err(HshError::InvalidParams(_))
+        ));
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert!(json.contains("\"m_cost\":64"), "{}", json);
+        let tampered = json.replace("\"m_cost\":64", "\"m_cost\":16");
+        assert!(serde_json::from_str::<Hash>(&tampered).is_err());
+        assert_eq!(serde_json::from_str::<Hash>(&json).unwrap(), hash);
+    }
 }
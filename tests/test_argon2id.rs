@@ -0,0 +1,127 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the Argon2id hashing algorithm.
+
+#[cfg(test)]
+mod tests {
+    use hsh::algorithms::argon2i::Argon2i;
+    use hsh::algorithms::argon2id::Argon2id;
+    use hsh::models::error::HshError;
+    use hsh::models::hash::Hash;
+    use hsh::models::hash_algorithm::{
+        HashAlgorithm, HashingAlgorithm,
+    };
+
+    #[test]
+    fn test_hash_differs_from_password() {
+        let password = "password123";
+        let salt = "somesalt";
+        let hashed_password =
+            Argon2id::hash_password(password, salt).unwrap();
+
+        assert_ne!(hashed_password, password.as_bytes());
+    }
+
+    #[test]
+    fn test_different_salts_produce_different_hashes() {
+        let password = "password123";
+        let salt1 = "salt123456789012345678901234567";
+        let salt2 = "salt234567890123456789012345678";
+
+        let hash1 = Argon2id::hash_password(password, salt1).unwrap();
+        let hash2 = Argon2id::hash_password(password, salt2).unwrap();
+
+        assert_ne!(hash1, hash2);
+    }
+
+    #[test]
+    fn test_same_password_and_salt_produce_same_hash() {
+        let password = "password123";
+        let salt = "somesalt";
+
+        let hash1 = Argon2id::hash_password(password, salt).unwrap();
+        let hash2 = Argon2id::hash_password(password, salt).unwrap();
+
+        assert_eq!(hash1, hash2);
+    }
+
+    #[test]
+    fn test_hash_password_length() {
+        let password = "password123";
+        let salt = "somesalt";
+        let hashed_password =
+            Argon2id::hash_password(password, salt).unwrap();
+
+        assert_eq!(hashed_password.len(), 32);
+    }
+
+    #[test]
+    fn test_digest_differs_from_argon2i() {
+        let password = "password123";
+        let salt = "somesalt";
+
+        assert_ne!(
+            Argon2id::hash_password(password, salt).unwrap(),
+            Argon2i::hash_password(password, salt).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_short_salt_is_rejected() {
+        let result = Argon2id::hash_password("password123", "short");
+        assert_eq!(
+            result,
+            Err(HshError::SaltTooShort { min: 8, found: 5 }.to_string())
+        );
+    }
+
+    #[test]
+    fn test_new_and_verify() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2id").unwrap();
+        assert_eq!(hash.algorithm, HashAlgorithm::Argon2id);
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(hash.verify("password456"), Ok(false));
+    }
+
+    #[test]
+    fn test_algorithm_from_str() {
+        assert_eq!(
+            "argon2id".parse::<HashAlgorithm>(),
+            Ok(HashAlgorithm::Argon2id)
+        );
+        assert_eq!(
+            Hash::parse_algorithm("$argon2id$v=19"),
+            Ok(HashAlgorithm::Argon2id)
+        );
+    }
+
+    #[test]
+    fn test_phc_string_round_trip() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2id").unwrap();
+        let stored = hash.to_phc_string().unwrap();
+        assert!(stored.starts_with("$argon2id$v=19$m=4096,t=3,p=1$"));
+
+        let parsed = hsh::phc::parse(&stored).unwrap();
+        assert_eq!(parsed.algorithm, HashAlgorithm::Argon2id);
+        assert_eq!(parsed, hash);
+        assert_eq!(parsed.verify("password123"), Ok(true));
+    }
+
+    #[test]
+    fn test_binary_and_serde_round_trip() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2id").unwrap();
+
+        assert_eq!(
+            Hash::from_bytes(&hash.to_bytes().unwrap()).unwrap(),
+            hash
+        );
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert!(json.contains("\"Argon2id\""));
+        assert_eq!(serde_json::from_str::<Hash>(&json).unwrap(), hash);
+    }
+}
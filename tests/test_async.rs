@@ -0,0 +1,47 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the async API, run with `--features async`.
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use hsh::models::hash::Hash;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[tokio::test]
+    async fn test_verify_async_does_not_block_runtime() {
+        let hash = Hash::new_async(
+            String::from("password123"),
+            String::from("somesalt"),
+            String::from("scrypt"),
+        )
+        .await
+        .unwrap();
+
+        // On this single-threaded runtime, the ticker only makes
+        // progress while verification runs if it does not block.
+        let done = AtomicBool::new(false);
+        let mut ticks = 0u64;
+        let verify = async {
+            let verified =
+                hash.verify_async(String::from("password123")).await;
+            done.store(true, Ordering::SeqCst);
+            verified
+        };
+        let ticker = async {
+            while !done.load(Ordering::SeqCst) {
+                ticks += 1;
+                tokio::task::yield_now().await;
+            }
+        };
+        let (verified, ()) = tokio::join!(verify, ticker);
+
+        assert_eq!(verified, Ok(true));
+        assert!(ticks > 0);
+        assert_eq!(
+            hash.verify_async(String::from("wrongpassword")).await,
+            Ok(false)
+        );
+        assert_eq!(hash.verify_async("password123").await, Ok(true));
+    }
+}
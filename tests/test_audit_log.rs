@@ -0,0 +1,67 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the audit records written by `Hash::verify_with_subject`.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::hash::{Hash, AUDIT_LOG_TARGET};
+    use log::{LevelFilter, Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    // Logger collecting audit records so the test can inspect them
+    struct CaptureLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl Log for CaptureLogger {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == AUDIT_LOG_TARGET
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            if self.enabled(record.metadata()) {
+                self.messages
+                    .lock()
+                    .unwrap()
+                    .push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_verify_with_subject_logs_subject_fingerprint() {
+        let logger: &'static CaptureLogger =
+            Box::leak(Box::new(CaptureLogger {
+                messages: Mutex::new(Vec::new()),
+            }));
+        log::set_logger(logger).unwrap();
+        log::set_max_level(LevelFilter::Info);
+
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let subject = "alice@example.com";
+        assert_eq!(
+            hash.verify_with_subject("password123", subject),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_with_subject("wrongpassword", subject),
+            Ok(false)
+        );
+
+        // The first 8 bytes of SHA-256("alice@example.com").
+        let fingerprint = "ff8d9819fc0e12bf";
+        let messages = logger.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        for message in messages.iter() {
+            assert!(message.contains(fingerprint));
+            assert!(!message.contains(subject));
+            assert!(!message.contains("password123"));
+        }
+        assert!(messages[0].contains("succeeded"));
+        assert!(messages[1].contains("failed"));
+    }
+}
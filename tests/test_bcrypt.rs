@@ -1,11 +1,12 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the Bcrypt hashing algorithm.
+
 #[cfg(test)]
 mod tests {
     use hsh::algorithms::bcrypt::Bcrypt;
-    use hsh::models::hash::
-    Hash;
+    use hsh::models::hash::Hash;
     use hsh::models::hash_algorithm::{
         HashAlgorithm, HashingAlgorithm,
     };
@@ -52,7 +53,7 @@ mod tests {
 
         assert_eq!(hash.algorithm, HashAlgorithm::Bcrypt);
         assert!(!hash.hash.is_empty());
-        assert_eq!(hash.salt.len(), 0);
+        assert_eq!(hash.salt.len(), 16);
     }
 
     #[test]
@@ -76,7 +77,10 @@ mod tests {
     fn test_from_hash_error() {
         let hash_bytes = vec![1, 2, 3, 4];
         let hash = Hash::from_hash(&hash_bytes, "invalid").unwrap_err();
-        assert_eq!(hash, "Unsupported hash algorithm: invalid");
+        assert_eq!(
+            hash.to_string(),
+            "Unsupported hash algorithm: invalid"
+        );
     }
 
     #[test]
@@ -87,4 +91,41 @@ mod tests {
         assert!(hash.verify(password).unwrap());
         assert!(!hash.verify("wrong_password").unwrap());
     }
+
+    #[test]
+    fn test_bcrypt_cost() {
+        let hash = Hash::new("password123", "", "bcrypt").unwrap();
+        assert_eq!(hash.bcrypt_cost(), Some(bcrypt::DEFAULT_COST));
+
+        let hash = Hash::new_bcrypt("password123", 5).unwrap();
+        assert_eq!(hash.bcrypt_cost(), Some(5));
+
+        // The cost survives a serialization round trip.
+        let json = serde_json::to_string(&hash).unwrap();
+        let restored: Hash = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.bcrypt_cost(), Some(5));
+
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        assert_eq!(hash.bcrypt_cost(), None);
+    }
+
+    #[test]
+    fn test_to_phc_string_bcrypt() {
+        // The modular crypt string is already in PHC shape and is
+        // returned as is, whatever the revision prefix.
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        let phc = hash.to_phc_string().unwrap();
+        assert_eq!(phc.as_bytes(), hash.hash());
+
+        let parsed = Hash::from_string(&phc).unwrap();
+        assert_eq!(parsed.algorithm, HashAlgorithm::Bcrypt);
+        assert!(parsed.verify("password123").unwrap());
+        assert!(!parsed.verify("wrong_password").unwrap());
+
+        let revision_2y = phc.replacen("$2b$", "$2y$", 1);
+        let parsed = Hash::from_string(&revision_2y).unwrap();
+        assert_eq!(parsed.to_phc_string().unwrap(), revision_2y);
+        assert!(parsed.verify("password123").unwrap());
+    }
 }
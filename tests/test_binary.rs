@@ -0,0 +1,59 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the compact binary encoding of a `Hash`.
+
+#[cfg(test)]
+mod tests {
+    use hsh::binary::FORMAT_VERSION;
+    use hsh::models::{error::HshError, hash::Hash};
+
+    #[test]
+    fn test_to_bytes_round_trip() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+            Hash::from_hash(b"legacy digest", "argon2i").unwrap(),
+        ];
+        for hash in hashes {
+            let bytes = hash.to_bytes().unwrap();
+            assert_eq!(bytes[0], FORMAT_VERSION);
+            assert_eq!(Hash::from_bytes(&bytes).unwrap(), hash);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_truncated_buffer() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let bytes = hash.to_bytes().unwrap();
+
+        for len in 0..bytes.len() {
+            assert!(matches!(
+                Hash::from_bytes(&bytes[..len]),
+                Err(HshError::CorruptStoredHash(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_unknown_format_and_trailing_bytes() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+
+        let mut bytes = hash.to_bytes().unwrap();
+        bytes[0] = FORMAT_VERSION + 1;
+        assert!(matches!(
+            Hash::from_bytes(&bytes),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+
+        let mut bytes = hash.to_bytes().unwrap();
+        bytes.push(0);
+        assert!(matches!(
+            Hash::from_bytes(&bytes),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the bincode encoding, run with `--features bincode`.
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use hsh::models::{
+        error::HshError, hash::Hash, hash_algorithm::HashAlgorithm,
+    };
+
+    #[test]
+    fn test_bincode_round_trips_every_algorithm() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new("password123", "somesalt", "argon2id").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        let algorithms = [
+            HashAlgorithm::Argon2i,
+            HashAlgorithm::Argon2id,
+            HashAlgorithm::Bcrypt,
+            HashAlgorithm::Scrypt,
+        ];
+        for (hash, algorithm) in hashes.iter().zip(algorithms) {
+            let bytes = hash.to_bincode().unwrap();
+            let decoded = Hash::from_bincode(&bytes).unwrap();
+
+            assert_eq!(&decoded, hash);
+            assert_eq!(decoded.algorithm, algorithm);
+            assert_eq!(decoded.verify("password123"), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_bincode_rejects_malformed_input() {
+        let bytes = Hash::new("password123", "somesalt", "scrypt")
+            .unwrap()
+            .to_bincode()
+            .unwrap();
+
+        assert!(matches!(
+            Hash::from_bincode(&bytes[..bytes.len() / 2]),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+        assert!(matches!(
+            Hash::from_bincode(&[]),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the `CompromisedFilter` struct.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::{
+        compromised_filter::CompromisedFilter, hash::Hash,
+    };
+
+    #[test]
+    fn test_filter_insert_and_contains() {
+        let mut filter = CompromisedFilter::new();
+        assert!(filter.is_empty());
+
+        assert!(filter.insert(vec![1, 2, 3]));
+        assert!(!filter.insert(vec![1, 2, 3]));
+        assert_eq!(filter.len(), 1);
+        assert!(filter.contains(&[1, 2, 3]));
+        assert!(!filter.contains(&[1, 2]));
+    }
+
+    #[test]
+    fn test_is_compromised() {
+        let leaked =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let other =
+            Hash::new("password123", "othersalt", "argon2i").unwrap();
+        let filter =
+            CompromisedFilter::from_digests([leaked.hash.clone()]);
+
+        assert!(leaked.is_compromised(&filter));
+        // Same password, different salt: a different digest.
+        assert!(!other.is_compromised(&filter));
+    }
+}
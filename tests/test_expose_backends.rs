@@ -0,0 +1,57 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the backend escape hatch, run with
+//! `--features expose-backends`.
+
+#[cfg(all(test, feature = "expose-backends"))]
+mod tests {
+    use hsh::algorithms::{argon2i::Argon2i, scrypt::Scrypt};
+    use hsh::backends::{argon2, scrypt};
+    use hsh::models::{error::HshError, hash::Hash, params::Params};
+
+    #[test]
+    fn test_argon2_backend_hashes_like_the_wrapper() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let argon2: argon2::Argon2<'static> =
+            Argon2i::backend(hash.parameters()).unwrap();
+
+        let mut output = [0u8; 32];
+        argon2
+            .hash_password_into(
+                b"password123",
+                b"somesalt",
+                &mut output,
+            )
+            .unwrap();
+        assert_eq!(output.as_slice(), hash.hash());
+    }
+
+    #[test]
+    fn test_scrypt_backend_hashes_like_the_wrapper() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let params: scrypt::Params =
+            Scrypt::backend(hash.parameters()).unwrap();
+        assert_eq!(params.log_n(), 14);
+
+        let mut output = [0u8; 64];
+        scrypt::scrypt(
+            b"password123",
+            b"somesalt",
+            &params,
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(output.as_slice(), hash.hash());
+    }
+
+    #[test]
+    fn test_backend_rejects_other_parameters() {
+        assert!(matches!(
+            Scrypt::backend(Params::Bcrypt { cost: 4 }),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the global default options. They live in their own test
+//! binary, as the options can only be set once per process.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::{
+        error::HshError,
+        hash::Hash,
+        hash_algorithm::HashAlgorithm,
+        hash_options::{
+            global_defaults, set_global_defaults, HashOptions,
+        },
+        params::Params,
+    };
+
+    #[test]
+    fn test_new_uses_global_defaults() {
+        assert_eq!(global_defaults(), None);
+
+        let options = HashOptions::new(
+            HashAlgorithm::Scrypt,
+            Params::Scrypt {
+                log_n: 10,
+                r: 8,
+                p: 1,
+            },
+        )
+        .unwrap();
+        set_global_defaults(options).unwrap();
+        assert_eq!(global_defaults(), Some(options));
+
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        assert_eq!(hash.parameters(), options.params);
+        assert!(hash.verify("password123").unwrap());
+        assert!(hash
+            .to_phc_string()
+            .unwrap()
+            .starts_with("$scrypt$ln=10,r=8,p=1$"));
+
+        // Other algorithms keep the library defaults.
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        assert_eq!(
+            hash.parameters(),
+            Params::default_for(HashAlgorithm::Argon2i)
+        );
+
+        // The options can only be set once.
+        let other = HashOptions::default_for(HashAlgorithm::Argon2i);
+        assert!(matches!(
+            set_global_defaults(other),
+            Err(HshError::InvalidParams(_))
+        ));
+        assert_eq!(global_defaults(), Some(options));
+    }
+}
@@ -1,11 +1,22 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the `Hash` struct and its builder.
+
 #[cfg(test)]
 mod tests {
+    use hsh::algorithms::argon2i::Argon2i;
+    use hsh::algorithms::scrypt::Scrypt;
+    use hsh::models::error::HshError;
     use hsh::models::hash::{Hash, HashBuilder, Salt};
-    use hsh::models::hash_algorithm::HashAlgorithm;
+    use hsh::models::hash_algorithm::{HashAlgorithm, SecurityLevel};
+    use hsh::models::hash_options::HashOptions;
+    use hsh::models::params::Params;
+    use hsh::models::prehash::Prehash;
+    use hsh::models::salt_encoding::SaltEncoding;
+    use hsh::models::salt_source::{SaltRng, SaltSource};
     use std::str::FromStr;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_new_argon2i() {
@@ -27,12 +38,40 @@ mod tests {
     #[test]
     fn test_new_scrypt() {
         let password = "password123";
-        let salt: Salt = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let salt: Salt = (0..16).collect();
         let hash = Hash::new_scrypt(password, salt.clone()).unwrap();
         assert_eq!(hash.salt, salt);
         assert_eq!(hash.algorithm, HashAlgorithm::Scrypt);
     }
 
+    #[test]
+    fn test_new_scrypt_rejects_short_salt() {
+        let salt: Salt = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        assert_eq!(
+            Hash::new_scrypt("password123", salt.clone()),
+            Err(HshError::SaltTooShort { min: 16, found: 10 })
+        );
+        assert_eq!(
+            Hash::new_scrypt_with_min_salt_len(
+                "password123",
+                salt.clone(),
+                12
+            ),
+            Err(HshError::SaltTooShort { min: 12, found: 10 })
+        );
+        assert!(Hash::new_scrypt_with_min_salt_len(
+            "password123",
+            salt.clone(),
+            10
+        )
+        .is_ok());
+
+        let migrated =
+            Hash::new_scrypt_unchecked("password123", salt).unwrap();
+        assert_eq!(migrated.verify("password123"), Ok(true));
+    }
+
     #[test]
     fn test_from_hash() {
         let hash_bytes = vec![1, 2, 3, 4];
@@ -64,5 +103,1983 @@ mod tests {
         assert_eq!(built_hash.algorithm, algorithm);
     }
 
-    // Add more tests such as verification, string representation, etc.
+    #[test]
+    fn test_verify_corrupt_argon2i_hash() {
+        let mut hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        hash.hash = vec![1, 2, 3, 4, 5];
+
+        assert!(matches!(
+            hash.verify("password123"),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+
+    #[test]
+    fn test_hash_zeroizes_on_drop() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Hash>();
+
+        // Wiping on drop does not get in the way of cloning and
+        // serializing.
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let copy = hash.clone();
+        let json = serde_json::to_string(&hash).unwrap();
+        drop(hash);
+        assert_eq!(copy.verify("password123"), Ok(true));
+        assert_eq!(serde_json::from_str::<Hash>(&json).unwrap(), copy);
+    }
+
+    #[test]
+    fn test_verify_corrupt_argon2i_salt() {
+        let mut hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        hash.salt = b"salt".to_vec();
+
+        assert!(matches!(
+            hash.verify("password123"),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_corrupt_bcrypt_hash() {
+        let hash = Hash::from_hash(
+            b"$2b$04$not-valid-base64!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!",
+            "bcrypt",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            hash.verify("password123"),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_corrupt_scrypt_hash() {
+        let mut hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let truncated = hash.hash()[..8].to_vec();
+        hash.hash = truncated;
+
+        assert!(matches!(
+            hash.verify("password123"),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_malformed_hash_fails_fast() {
+        // Parameters that would take minutes to recompute: the
+        // truncated digest is rejected before the KDF runs.
+        let mut hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        hash.params = Some(Params::Scrypt {
+            log_n: 20,
+            r: 8,
+            p: 16,
+        });
+        hash.hash.truncate(8);
+
+        let start = Instant::now();
+        assert!(matches!(
+            hash.verify("password123"),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // A Bcrypt hash holding the PHC string of another algorithm
+        // is a mismatch, not a failed password.
+        let argon2i =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let mut bcrypt = Hash::new_bcrypt("password123", 4).unwrap();
+        bcrypt.hash = argon2i.to_phc_string().unwrap().into_bytes();
+
+        assert_eq!(
+            bcrypt.verify("password123"),
+            Err(HshError::AlgorithmMismatch {
+                expected: HashAlgorithm::Bcrypt,
+                found: HashAlgorithm::Argon2i,
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_wrong_password_is_not_corruption() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+
+        assert_eq!(hash.verify("wrongpassword"), Ok(false));
+    }
+
+    #[test]
+    fn test_to_phc_string_round_trip() {
+        for (algo, prefix) in [
+            ("argon2i", "$argon2i$v=19$m=4096,t=3,p=1$c29tZXNhbHQ$"),
+            ("argon2id", "$argon2id$v=19$m=4096,t=3,p=1$c29tZXNhbHQ$"),
+            ("scrypt", "$scrypt$ln=14,r=8,p=1$c29tZXNhbHQ$"),
+        ] {
+            let hash =
+                Hash::new("password123", "somesalt", algo).unwrap();
+            let phc = hash.to_phc_string().unwrap();
+            assert!(phc.starts_with(prefix), "{}", phc);
+
+            let parsed = Hash::from_string(&phc).unwrap();
+            assert_eq!(parsed, hash);
+            assert_eq!(parsed.verify("password123"), Ok(true));
+        }
+
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        let phc = hash.to_phc_string().unwrap();
+        assert!(phc.starts_with("$2b$04$"));
+        assert!(Hash::from_string(&phc)
+            .unwrap()
+            .verify("password123")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_write_phc_matches_to_phc_string() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+        ];
+
+        for hash in &hashes {
+            let mut buf = Vec::new();
+            hash.write_phc(&mut buf).unwrap();
+
+            assert_eq!(buf, hash.to_phc_string().unwrap().into_bytes());
+        }
+    }
+
+    #[test]
+    fn test_phc_equivalent() {
+        let a = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+        let b = "$argon2i$v=16$p=1,m=256,t=2$c29tZXNhbHQ=$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg=";
+        let c = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$AAAApGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+        let d = "$argon2i$v=16$m=512,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+
+        assert_eq!(Hash::phc_equivalent(a, b), Ok(true));
+        assert_eq!(Hash::phc_equivalent(a, c), Ok(false));
+        assert_eq!(Hash::phc_equivalent(a, d), Ok(false));
+        assert_eq!(
+            Hash::phc_equivalent(a, "not a hash"),
+            Err(HshError::InvalidHashString)
+        );
+    }
+
+    #[test]
+    fn test_verify_meets_time_floor() {
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        let floor = Duration::from_millis(300);
+
+        let start = Instant::now();
+        let verified = hash.verify_constant_time_across_algorithms(
+            "password123",
+            floor,
+        );
+        assert_eq!(verified, Ok(true));
+        assert!(start.elapsed() >= floor);
+
+        let start = Instant::now();
+        let verified = hash.verify_constant_time_across_algorithms(
+            "wrongpassword",
+            floor,
+        );
+        assert_eq!(verified, Ok(false));
+        assert!(start.elapsed() >= floor);
+    }
+
+    #[test]
+    fn test_verify_floor_pads_malformed_hashes() {
+        // A stored hash too short to verify fails before any key
+        // derivation, yet still takes the whole floor.
+        let mut hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        hash.hash = vec![0u8; 2];
+        let floor = Duration::from_millis(200);
+
+        let start = Instant::now();
+        let verified = hash.verify_constant_time_across_algorithms(
+            "password123",
+            floor,
+        );
+        assert!(matches!(
+            verified,
+            Err(HshError::CorruptStoredHash(_))
+        ));
+        assert!(start.elapsed() >= floor);
+    }
+
+    #[test]
+    fn test_bcrypt_salt_is_decoded() {
+        let phc = "$2b$04$KBCwKxOzLha2MUDgW0PjXeaTzotHTXw8StzE.taZVR2tApYiPTOmy";
+        let hash = Hash::from_string(phc).unwrap();
+
+        assert_eq!(hash.salt().len(), 16);
+        assert_eq!(hash.salt_encoded(), "KBCwKxOzLha2MUDgW0PjXe");
+
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        assert_eq!(hash.salt().len(), 16);
+        assert_eq!(
+            hash.salt_encoded(),
+            hash.to_phc_string().unwrap()[7..29]
+        );
+
+        // The salt given to `new` is ignored by Bcrypt, and replaced
+        // by the embedded one.
+        let hash =
+            Hash::new("password123", "somesalt", "bcrypt").unwrap();
+        assert_eq!(hash.salt().len(), 16);
+        assert_eq!(
+            hash.salt_encoded(),
+            hash.to_phc_string().unwrap()[7..29]
+        );
+    }
+
+    #[test]
+    fn test_verify_with_hex_salt_encoding() {
+        let mut hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        hash.salt = b"736F6D6573616C74".to_vec();
+
+        assert_eq!(hash.verify("password123"), Ok(false));
+        assert_eq!(
+            hash.verify_with_salt_encoding(
+                "password123",
+                SaltEncoding::Hex
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_with_salt_encoding(
+                "wrongpassword",
+                SaltEncoding::Hex
+            ),
+            Ok(false)
+        );
+        assert_eq!(
+            hash.verify_with_salt_encoding(
+                "password123",
+                SaltEncoding::Raw
+            ),
+            Ok(false)
+        );
+
+        hash.salt = b"736f6d6573616c7".to_vec();
+        assert!(matches!(
+            hash.verify_with_salt_encoding(
+                "password123",
+                SaltEncoding::Hex
+            ),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+    }
+
+    #[test]
+    fn test_hash_sequential_hashes_verify_independently() {
+        let options = HashOptions::new(
+            HashAlgorithm::Argon2i,
+            Params::Argon2 {
+                version: 0x13,
+                m_cost: 32,
+                t_cost: 1,
+                p_cost: 1,
+            },
+        )
+        .unwrap();
+        let items: Vec<(String, String)> = (0..1000)
+            .map(|i| {
+                (format!("password{}", i), format!("somesalt{}", i))
+            })
+            .collect();
+
+        let hashes: Vec<Hash> =
+            Hash::hash_sequential(items.clone(), options)
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        assert_eq!(hashes.len(), 1000);
+        for ((password, salt), hash) in items.iter().zip(&hashes) {
+            assert_eq!(hash.salt(), salt.as_bytes());
+            assert_eq!(hash.verify(password), Ok(true));
+        }
+        assert_eq!(hashes[0].verify("password1"), Ok(false));
+    }
+
+    #[test]
+    fn test_hash_sequential_argon2id() {
+        let options = HashOptions::new(
+            HashAlgorithm::Argon2id,
+            Params::Argon2 {
+                version: 0x13,
+                m_cost: 32,
+                t_cost: 1,
+                p_cost: 1,
+            },
+        )
+        .unwrap();
+
+        let hash = Hash::hash_sequential(
+            [("password123", "somesalt")],
+            options,
+        )
+        .next()
+        .unwrap()
+        .unwrap();
+        assert_eq!(hash.algorithm(), HashAlgorithm::Argon2id);
+        assert_eq!(hash.hash_length(), 32);
+        assert_eq!(hash.verify("password123"), Ok(true));
+    }
+
+    #[test]
+    fn test_hash_sequential_rejects_non_argon2_options() {
+        let options = HashOptions::new(
+            HashAlgorithm::Scrypt,
+            Params::default_for(HashAlgorithm::Scrypt),
+        )
+        .unwrap();
+        let mut hashes = Hash::hash_sequential(
+            [("password123", "somesalt")],
+            options,
+        );
+
+        assert!(matches!(
+            hashes.next(),
+            Some(Err(HshError::InvalidParams(_)))
+        ));
+    }
+
+    #[test]
+    fn test_verify_components_argon2i() {
+        let phc = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+        let stored = Hash::from_string(phc).unwrap();
+        let params = Params::Argon2 {
+            version: 0x10,
+            m_cost: 256,
+            t_cost: 2,
+            p_cost: 1,
+        };
+
+        for (password, expected) in
+            [("password123", true), ("wrongpassword", false)]
+        {
+            assert_eq!(
+                Hash::verify_components(
+                    stored.hash(),
+                    b"somesalt",
+                    HashAlgorithm::Argon2i,
+                    &params,
+                    password,
+                ),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_components_scrypt() {
+        let stored =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let params = Params::default_for(HashAlgorithm::Scrypt);
+
+        for (password, expected) in
+            [("password123", true), ("wrongpassword", false)]
+        {
+            assert_eq!(
+                Hash::verify_components(
+                    stored.hash(),
+                    b"somesalt",
+                    HashAlgorithm::Scrypt,
+                    &params,
+                    password,
+                ),
+                Ok(expected)
+            );
+        }
+
+        assert!(matches!(
+            Hash::verify_components(
+                stored.hash(),
+                b"somesalt",
+                HashAlgorithm::Argon2i,
+                &params,
+                "password123",
+            ),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_reconstruct_argon2i_verifies() {
+        let original =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        // The components as they might be stored in separate columns.
+        let digest = original.hash().to_vec();
+        let salt = original.salt().to_vec();
+        let params = original.parameters();
+
+        let rebuilt = Hash::reconstruct(
+            &digest,
+            &salt,
+            HashAlgorithm::Argon2i,
+            params,
+        )
+        .unwrap();
+        assert_eq!(rebuilt, original);
+        assert_eq!(rebuilt.verify("password123"), Ok(true));
+        assert_eq!(rebuilt.verify("password456"), Ok(false));
+
+        // `from_hash` drops the salt, so its hash cannot verify.
+        let lossy = Hash::from_hash(&digest, "argon2i").unwrap();
+        assert_ne!(lossy.verify("password123"), Ok(true));
+
+        assert!(matches!(
+            Hash::reconstruct(
+                &digest,
+                &salt,
+                HashAlgorithm::Scrypt,
+                params
+            ),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_argon2i_phc_encoding_never_errors() {
+        let built = HashBuilder::new()
+            .hash(vec![7; 32])
+            .salt(b"somesalt".to_vec())
+            .algorithm(HashAlgorithm::Argon2i)
+            .build()
+            .unwrap();
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new_argon2i("password123", b"othersalt".to_vec())
+                .unwrap(),
+            built,
+        ];
+
+        for hash in &hashes {
+            let phc = hash.to_phc_string();
+            assert!(phc.is_ok());
+            assert_eq!(hash.display_phc().to_string(), phc.unwrap());
+        }
+    }
+
+    #[test]
+    fn test_verify_with_hook_reports_outcome_once() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+
+        for (password, expected) in
+            [("password123", true), ("wrongpassword", false)]
+        {
+            let mut outcomes = Vec::new();
+            let verified = hash.verify_with_hook(password, &mut |ok| {
+                outcomes.push(ok)
+            });
+
+            assert_eq!(verified, Ok(expected));
+            assert_eq!(outcomes, vec![expected]);
+        }
+
+        let mut corrupt = hash.clone();
+        corrupt.hash = vec![0; 4];
+        let mut calls = 0;
+        assert!(corrupt
+            .verify_with_hook("password123", &mut |_| calls += 1)
+            .is_err());
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_algorithm_names_are_normalized() {
+        for (name, expected) in [
+            ("Argon2i", HashAlgorithm::Argon2i),
+            ("ARGON2I", HashAlgorithm::Argon2i),
+            ("bcrypt ", HashAlgorithm::Bcrypt),
+            ("  SCrypt\t", HashAlgorithm::Scrypt),
+        ] {
+            assert_eq!(HashAlgorithm::from_str(name), Ok(expected));
+            assert_eq!(
+                Hash::from_hash(&[1, 2, 3], name).unwrap().algorithm(),
+                expected
+            );
+            assert!(Hash::generate_salt(name).is_ok());
+            assert!(Hash::generate_salt_bytes(name).is_ok());
+            assert_eq!(
+                Hash::parse_algorithm(&format!("${}$", name)),
+                Ok(expected)
+            );
+        }
+
+        let hash =
+            Hash::new("password123", "somesalt", " Scrypt ").unwrap();
+        assert_eq!(hash.algorithm(), HashAlgorithm::Scrypt);
+        assert_eq!(
+            Hash::generate_hash("password123", "somesalt", "SCRYPT"),
+            Ok(hash.hash().to_vec())
+        );
+
+        assert!(
+            Hash::new("password123", "somesalt", "argon 2i").is_err()
+        );
+    }
+
+    #[test]
+    fn test_argon2i_phc_preserves_version() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let phc = hash.to_phc_string().unwrap();
+        assert!(phc.starts_with("$argon2i$v=19$"));
+
+        let parsed = Hash::from_string(&phc).unwrap();
+        assert!(matches!(
+            parsed.params,
+            Some(Params::Argon2 { version: 0x13, .. })
+        ));
+        assert_eq!(parsed.verify("password123"), Ok(true));
+        assert_eq!(parsed.to_phc_string().unwrap(), phc);
+
+        let legacy = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+        let parsed = Hash::from_string(legacy).unwrap();
+        assert_eq!(parsed.verify("password123"), Ok(true));
+        assert_eq!(parsed.to_phc_string().unwrap(), legacy);
+    }
+
+    #[test]
+    fn test_argon2i_without_params_uses_legacy_version() {
+        let phc = "$argon2i$v=16$m=4096,t=3,p=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+        let mut hash = Hash::from_string(phc).unwrap();
+        hash.params = None;
+
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(hash.to_phc_string().unwrap(), phc);
+    }
+
+    #[test]
+    fn test_parameters_for_each_algorithm() {
+        let argon2i =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        match argon2i.parameters() {
+            Params::Argon2 {
+                version,
+                m_cost,
+                t_cost,
+                p_cost,
+            } => assert_eq!(
+                (version, m_cost, t_cost, p_cost),
+                (0x13, 4096, 3, 1)
+            ),
+            other => panic!("unexpected parameters: {:?}", other),
+        }
+
+        let bcrypt = Hash::from_string(
+            "$2b$04$KBCwKxOzLha2MUDgW0PjXeaTzotHTXw8StzE.taZVR2tApYiPTOmy",
+        )
+        .unwrap();
+        match bcrypt.parameters() {
+            Params::Bcrypt { cost } => assert_eq!(cost, 4),
+            other => panic!("unexpected parameters: {:?}", other),
+        }
+
+        let scrypt =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        match scrypt.parameters() {
+            Params::Scrypt { log_n, r, p } => {
+                assert_eq!((log_n, r, p), (14, 8, 1))
+            }
+            other => panic!("unexpected parameters: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_security_assessment_bcrypt_cost() {
+        let weak = Hash::new_bcrypt("password123", 6).unwrap();
+        let assessment = weak.security_assessment();
+        assert!(assessment.urgent_rehash);
+        assert_eq!(assessment.params, Params::Bcrypt { cost: 6 });
+        assert_eq!(assessment.findings.len(), 1);
+
+        let strong = Hash::new_bcrypt("password123", 12).unwrap();
+        let assessment = strong.security_assessment();
+        assert!(!assessment.urgent_rehash);
+        assert!(assessment.findings.is_empty());
+    }
+
+    #[test]
+    fn test_security_assessment_argon2_memory() {
+        let weak = Hash::from_string("$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg").unwrap();
+        assert!(weak.security_assessment().urgent_rehash);
+
+        let default =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        assert!(!default.security_assessment().urgent_rehash);
+    }
+
+    #[test]
+    fn test_verify_trimmed_accepts_trailing_whitespace() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+
+        assert_eq!(hash.verify("password123 "), Ok(false));
+        assert_eq!(hash.verify_trimmed("password123 "), Ok(true));
+        assert_eq!(hash.verify_trimmed("password123"), Ok(true));
+        assert_eq!(hash.verify_trimmed(" password123"), Ok(false));
+        assert_eq!(hash.verify_trimmed("wrongpassword "), Ok(false));
+    }
+
+    #[test]
+    fn test_new_scrypt_with_params() {
+        let hash = Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            15,
+            8,
+            1,
+            32,
+        )
+        .unwrap();
+
+        assert_eq!(hash.hash_length(), 32);
+        assert_eq!(
+            hash.parameters(),
+            Params::Scrypt {
+                log_n: 15,
+                r: 8,
+                p: 1
+            }
+        );
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        let parsed =
+            Hash::from_string(&hash.to_phc_string().unwrap()).unwrap();
+        assert_eq!(parsed, hash);
+
+        assert!(Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            15,
+            8,
+            1,
+            128,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_scheme_carries_parameters_without_digest() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let scheme = hash.scheme();
+
+        assert_eq!(scheme.algorithm, HashAlgorithm::Scrypt);
+        assert_eq!(scheme.params, hash.parameters());
+
+        // Only the algorithm and parameters are serialized.
+        let json = serde_json::to_value(scheme).unwrap();
+        let mut keys: Vec<&String> =
+            json.as_object().unwrap().keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["algorithm", "params"]);
+    }
+
+    #[test]
+    fn test_verify_prehashed_hmac_sha256() {
+        // RFC 4231, test case 2.
+        let password = "what do ya want for nothing?";
+        let stored =
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+        let hash = Hash::new(stored, "somesalt", "argon2i").unwrap();
+        let prehash = Prehash::HmacSha256 {
+            key: b"Jefe".to_vec(),
+        };
+
+        assert_eq!(
+            hash.verify_prehashed(password, prehash.clone()),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_prehashed("wrong", prehash.clone()),
+            Ok(false)
+        );
+        assert_eq!(
+            hash.verify_prehashed(password, Prehash::Sha256),
+            Ok(false)
+        );
+        assert_eq!(
+            hash.verify_prehashed(stored, Prehash::None),
+            Ok(true)
+        );
+        assert!(!format!("{:?}", prehash).contains("Jefe"));
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_distinct() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let same =
+            Hash::from_string(&hash.to_phc_string().unwrap()).unwrap();
+        let other =
+            Hash::new("password456", "somesalt", "argon2i").unwrap();
+
+        assert_eq!(hash.fingerprint(), same.fingerprint());
+        assert_ne!(hash.fingerprint(), other.fingerprint());
+        assert!(hash
+            .fingerprint()
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase()));
+
+        // Hashes without a valid PHC form still get a fingerprint.
+        let mut corrupt = hash.clone();
+        corrupt.params = Some(Params::Bcrypt { cost: 4 });
+        assert_eq!(corrupt.fingerprint().len(), 8);
+        assert_ne!(corrupt.fingerprint(), hash.fingerprint());
+    }
+
+    #[test]
+    fn test_bcrypt_byte_api_rejects_non_utf8_password() {
+        let password = b"password\xff\xfe";
+
+        assert_eq!(
+            Hash::new_bytes(
+                password,
+                Vec::new(),
+                HashAlgorithm::Bcrypt
+            ),
+            Err(HshError::NonUtf8Password)
+        );
+
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        assert_eq!(
+            hash.verify_bytes(password),
+            Err(HshError::NonUtf8Password)
+        );
+        assert_eq!(hash.verify_bytes(b"password123"), Ok(true));
+    }
+
+    #[test]
+    fn test_byte_api_accepts_non_utf8_password() {
+        let password = b"password\xff\xfe";
+        for algorithm in [HashAlgorithm::Argon2i, HashAlgorithm::Scrypt]
+        {
+            let hash = Hash::new_bytes(
+                password,
+                b"somesalt".to_vec(),
+                algorithm,
+            )
+            .unwrap();
+            assert_eq!(hash.verify_bytes(password), Ok(true));
+            assert_eq!(hash.verify_bytes(b"password"), Ok(false));
+        }
+
+        // The byte API agrees with the string API for UTF-8 input.
+        let hash = Hash::new_bytes(
+            b"password123",
+            b"somesalt".to_vec(),
+            HashAlgorithm::Argon2i,
+        )
+        .unwrap();
+        assert_eq!(
+            hash,
+            Hash::new("password123", "somesalt", "argon2i").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_pairs_preserves_order() {
+        let argon2i =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let bcrypt = Hash::new_bcrypt("password456", 4).unwrap();
+        let mut corrupt = argon2i.clone();
+        corrupt.hash.truncate(2);
+
+        let pairs = vec![
+            (argon2i.clone(), String::from("password123")),
+            (bcrypt.clone(), String::from("password123")),
+            (bcrypt, String::from("password456")),
+            (corrupt, String::from("password123")),
+            (argon2i, String::from("password456")),
+        ];
+        let results = Hash::verify_pairs(&pairs);
+
+        assert_eq!(results.len(), pairs.len());
+        assert_eq!(results[0], Ok(true));
+        assert_eq!(results[1], Ok(false));
+        assert_eq!(results[2], Ok(true));
+        assert!(matches!(
+            results[3],
+            Err(HshError::CorruptStoredHash(_))
+        ));
+        assert_eq!(results[4], Ok(false));
+        assert!(Hash::verify_pairs(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_verify_candidates_finds_the_single_match() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let candidates = [
+            "123456",
+            "letmein",
+            "password123",
+            "qwerty",
+            "Password123",
+        ];
+
+        let results = hash.verify_candidates(&candidates);
+
+        assert_eq!(
+            results,
+            vec![Ok(false), Ok(false), Ok(true), Ok(false), Ok(false)]
+        );
+        assert!(hash.verify_candidates(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_verify_with_params_out() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new("password123", "somesalt", "argon2id").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new_scrypt_with_params(
+                "password123",
+                b"somesaltsomesalt".to_vec(),
+                10,
+                8,
+                1,
+                32,
+            )
+            .unwrap(),
+        ];
+        for hash in &hashes {
+            let stored = hash.params.unwrap();
+            assert_eq!(
+                hash.verify_with_params_out("password123"),
+                Ok((true, stored))
+            );
+            assert_eq!(
+                hash.verify_with_params_out("wrongpassword"),
+                Ok((false, stored))
+            );
+        }
+
+        // Hashes without recorded parameters verify with the legacy
+        // ones.
+        let mut legacy = hashes[0].clone();
+        legacy.params = None;
+        assert_eq!(
+            legacy.verify_with_params_out("password123"),
+            Ok((false, Params::legacy(HashAlgorithm::Argon2i)))
+        );
+    }
+
+    #[test]
+    fn test_needs_rehash() {
+        let bcrypt = Hash::new_bcrypt("password123", 4).unwrap();
+        assert!(bcrypt.needs_rehash(&Params::Bcrypt { cost: 10 }));
+        assert!(!bcrypt.needs_rehash(&Params::Bcrypt { cost: 4 }));
+
+        let scrypt =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let current = Params::default_for(HashAlgorithm::Scrypt);
+        assert!(!scrypt.needs_rehash(&current));
+        assert!(scrypt.needs_rehash(&Params::Scrypt {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }));
+        assert!(!scrypt.needs_rehash(&Params::Scrypt {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        }));
+
+        let argon2i =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let current = Params::default_for(HashAlgorithm::Argon2i);
+        assert!(!argon2i.needs_rehash(&current));
+        assert!(argon2i.needs_rehash(&Params::Argon2 {
+            version: 0x13,
+            m_cost: 19 * 1024,
+            t_cost: 2,
+            p_cost: 1,
+        }));
+
+        // An older Argon2 version is weaker whatever its costs.
+        let legacy = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x10,
+            8192,
+            4,
+            1,
+            32,
+        )
+        .unwrap();
+        assert!(legacy.needs_rehash(&current));
+
+        // Moving to another algorithm always needs a rehash.
+        assert!(bcrypt.needs_rehash(&current));
+    }
+
+    #[test]
+    fn test_verify_many_keeps_input_order() {
+        let passwords = ["letmein", "password123", "Password123"];
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new("password123", "somesalt", "argon2id").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        for hash in hashes {
+            assert_eq!(
+                hash.verify_many(&passwords),
+                vec![Ok(false), Ok(true), Ok(false)],
+                "{}",
+                hash.algorithm
+            );
+            assert_eq!(
+                hash.verify_many(&passwords),
+                hash.verify_candidates(&passwords)
+            );
+            assert!(hash.verify_many(&[]).is_empty());
+        }
+
+        // A broken hash fails for every password.
+        let mut corrupt =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        corrupt.hash.truncate(8);
+        let results = corrupt.verify_many(&passwords);
+        assert_eq!(results.len(), passwords.len());
+        assert!(results
+            .iter()
+            .all(|r| matches!(r, Err(HshError::CorruptStoredHash(_)))));
+    }
+
+    #[test]
+    fn test_verify_and_check_reports_deprecated_algorithm() {
+        // Memory-hard algorithms and Bcrypt at a sufficient cost are
+        // not deprecated.
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new_bcrypt("password123", 10).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        for hash in hashes {
+            let outcome = hash.verify_and_check("password123").unwrap();
+            assert!(outcome.verified);
+            assert!(!outcome.deprecated_algorithm);
+        }
+
+        // Bcrypt below `MIN_BCRYPT_COST` is.
+        let weak = Hash::new_bcrypt("password123", 4).unwrap();
+        assert_eq!(weak.security_level(), SecurityLevel::Deprecated);
+        let outcome = weak.verify_and_check("password123").unwrap();
+        assert!(outcome.verified);
+        assert!(outcome.deprecated_algorithm);
+
+        let weak = Hash::new_bcrypt("password123", 4).unwrap();
+        let outcome = weak.verify_and_check("wrongpassword").unwrap();
+        assert!(!outcome.verified);
+        assert!(outcome.urgent_rehash);
+    }
+
+    #[test]
+    fn test_verify_with_param_source_supplies_scrypt_params() {
+        let mut hash = Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            10,
+            8,
+            1,
+            64,
+        )
+        .unwrap();
+        hash.params = None;
+        // Without the parameters, the legacy defaults are assumed.
+        assert_eq!(hash.verify("password123"), Ok(false));
+
+        let source = |algorithm| {
+            assert_eq!(algorithm, HashAlgorithm::Scrypt);
+            Params::Scrypt {
+                log_n: 10,
+                r: 8,
+                p: 1,
+            }
+        };
+        assert_eq!(
+            hash.verify_with_param_source("password123", source),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_with_param_source("wrongpassword", source),
+            Ok(false)
+        );
+        assert!(matches!(
+            hash.verify_with_param_source("password123", |_| {
+                Params::Bcrypt { cost: 4 }
+            }),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_string_representation_ct_matches() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        for hash in hashes {
+            assert_eq!(
+                hash.to_string_representation_ct(),
+                hash.to_string_representation()
+            );
+        }
+
+        let all_bytes: Vec<u8> = (0..=255).collect();
+        let expected: String =
+            all_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hsh::hex::encode_ct(&all_bytes), expected);
+    }
+
+    #[test]
+    fn test_new_argon2i_padded_accepts_short_salt() {
+        assert!(
+            Hash::new_argon2i("password123", b"salt".to_vec()).is_err()
+        );
+
+        let hash =
+            Hash::new_argon2i_padded("password123", b"salt".to_vec())
+                .unwrap();
+        assert_eq!(hash.salt(), b"salt\0\0\0\0");
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(hash.verify("password456"), Ok(false));
+
+        // Salts that are long enough are used unchanged.
+        let hash = Hash::new_argon2i_padded(
+            "password123",
+            b"somesalt".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            hash,
+            Hash::new_argon2i("password123", b"somesalt".to_vec())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_with_heartbeat_beats_for_expensive_params() {
+        use std::cell::Cell;
+
+        // 2^14 * 8 * 128 bytes = 16 MiB, well over one heartbeat
+        // interval.
+        let hash = Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            14,
+            8,
+            1,
+            64,
+        )
+        .unwrap();
+        let beats = Cell::new(0);
+        let verified = hash
+            .verify_with_heartbeat("password123", || {
+                beats.set(beats.get() + 1)
+            })
+            .unwrap();
+        assert!(verified);
+        assert!(beats.get() >= 1);
+    }
+
+    #[test]
+    fn test_from_string_preserving_keeps_exact_input() {
+        let padded = "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ=$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg";
+        let mut hash = Hash::from_string_preserving(padded).unwrap();
+        assert_eq!(hash.original_phc(), Some(padded));
+        assert_ne!(hash.to_phc_string().unwrap(), padded);
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        assert_eq!(
+            Hash::from_string(padded).unwrap().original_phc(),
+            None
+        );
+
+        hash.set_salt("password123", b"othersalt").unwrap();
+        assert_eq!(hash.original_phc(), None);
+    }
+
+    #[test]
+    fn test_salt_source() {
+        let generated =
+            Hash::create("password123", None, "argon2i").unwrap();
+        assert_eq!(
+            generated.salt_source(),
+            SaltSource::Generated(SaltRng::OsRng)
+        );
+        assert_eq!(generated.verify("password123"), Ok(true));
+
+        let provided =
+            Hash::create("password123", Some("somesalt"), "scrypt")
+                .unwrap();
+        assert_eq!(provided.salt_source(), SaltSource::Provided);
+
+        let bcrypt =
+            Hash::create("password123", None, "bcrypt").unwrap();
+        assert_eq!(
+            bcrypt.salt_source(),
+            SaltSource::Generated(SaltRng::Backend)
+        );
+
+        // The source is not stored, and does not affect comparisons.
+        let parsed =
+            Hash::from_string(&provided.to_phc_string().unwrap())
+                .unwrap();
+        assert_eq!(parsed.salt_source(), SaltSource::Unknown);
+        assert_eq!(parsed, provided);
+    }
+
+    #[test]
+    fn test_argon2i_custom_tag_length_round_trips_through_phc() {
+        let hash = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x13,
+            256,
+            2,
+            1,
+            24,
+        )
+        .unwrap();
+        assert_eq!(hash.hash_length(), 24);
+
+        let phc = hash.to_phc_string().unwrap();
+        let parsed = Hash::from_string(&phc).unwrap();
+        assert_eq!(parsed.hash_length(), 24);
+        assert_eq!(parsed, hash);
+        assert_eq!(parsed.to_phc_string().unwrap(), phc);
+        assert_eq!(parsed.verify("password123"), Ok(true));
+        assert_eq!(parsed.verify("password456"), Ok(false));
+
+        assert!(Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x13,
+            256,
+            2,
+            1,
+            8,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_auto_format_accepts_legacy_and_phc() {
+        // Argon2i hashes from earlier releases used version 0x10,
+        // which the legacy format leaves implicit.
+        let phc = "$argon2i$v=16$m=4096,t=3,p=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+        let legacy =
+            Hash::from_string(phc).unwrap().to_string_representation();
+        assert!(legacy.starts_with("somesalt:"));
+
+        for stored in [phc, legacy.as_str()] {
+            assert_eq!(
+                Hash::verify_auto_format(
+                    stored,
+                    "password123",
+                    "argon2i"
+                ),
+                Ok(true)
+            );
+            assert_eq!(
+                Hash::verify_auto_format(
+                    stored,
+                    "password456",
+                    "argon2i"
+                ),
+                Ok(false)
+            );
+        }
+
+        let bcrypt = Hash::new_bcrypt("password123", 4).unwrap();
+        assert_eq!(
+            Hash::verify_auto_format(
+                &bcrypt.to_string_representation(),
+                "password123",
+                "bcrypt"
+            ),
+            Ok(true)
+        );
+
+        assert_eq!(
+            Hash::verify_auto_format(
+                "somesalt",
+                "password123",
+                "argon2i"
+            ),
+            Err(HshError::InvalidHashString)
+        );
+        assert!(matches!(
+            Hash::verify_auto_format(
+                "somesalt:xyz",
+                "password123",
+                "argon2i"
+            ),
+            Err(HshError::CorruptStoredHash(_))
+        ));
+        assert_eq!(
+            Hash::verify_auto_format(&legacy, "password123", "md5"),
+            Err(HshError::UnsupportedAlgorithm(String::from("md5")))
+        );
+    }
+
+    #[test]
+    fn test_verify_auto_format_current_argon2i() {
+        // Argon2i hashes from this release use version 0x13.
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let legacy = hash.to_string_representation();
+
+        assert_eq!(
+            Hash::verify_auto_format(&legacy, "password123", "argon2i"),
+            Ok(true)
+        );
+        assert_eq!(
+            Hash::verify_auto_format(&legacy, "password456", "argon2i"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_verify_accepts_borrowed_and_owned_passwords() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        let owned = String::from("password123");
+
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(hash.verify(&owned), Ok(true));
+        assert_eq!(hash.verify(owned), Ok(true));
+        assert_eq!(hash.verify(String::from("password456")), Ok(false));
+    }
+
+    #[test]
+    fn test_verify_with_retry_retries_transient_errors() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+
+        // The injected backend fails twice, then verifies for real.
+        let mut calls = 0;
+        let verified = hash.verify_with_retry_using(
+            "password123",
+            3,
+            |hash, pw| {
+                calls += 1;
+                if calls < 3 {
+                    Err(HshError::Backend(String::from(
+                        "out of memory",
+                    )))
+                } else {
+                    hash.verify(pw)
+                }
+            },
+        );
+        assert_eq!(verified, Ok(true));
+        assert_eq!(calls, 3);
+
+        // Attempts run out: the last error is returned.
+        let mut calls = 0;
+        let verified =
+            hash.verify_with_retry_using("password123", 2, |_, _| {
+                calls += 1;
+                Err(HshError::Backend(String::from("out of memory")))
+            });
+        assert_eq!(
+            verified,
+            Err(HshError::Backend(String::from("out of memory")))
+        );
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_verify_with_retry_does_not_retry_mismatch_or_corruption() {
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        assert_eq!(hash.verify_with_retry("password456", 5), Ok(false));
+
+        let mut calls = 0;
+        let verified =
+            hash.verify_with_retry_using("password123", 5, |_, _| {
+                calls += 1;
+                Err(HshError::CorruptStoredHash(String::from(
+                    "bad length",
+                )))
+            });
+        assert!(matches!(
+            verified,
+            Err(HshError::CorruptStoredHash(_))
+        ));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_backend_input_errors_are_not_retried() {
+        let mut output = [0u8; 32];
+        assert_eq!(
+            Argon2i::hash_with_params(
+                b"password123",
+                b"short",
+                0x13,
+                4096,
+                3,
+                1,
+                &mut output,
+            ),
+            Err(HshError::SaltTooShort { min: 8, found: 5 })
+        );
+        assert!(matches!(
+            Scrypt::hash_with_params(
+                b"password123",
+                b"somesalt",
+                10,
+                0,
+                1,
+                &mut output,
+            ),
+            Err(HshError::InvalidParams(_))
+        ));
+        assert_eq!(
+            Hash::generate_hash("password123", "short", "argon2i"),
+            Err(HshError::SaltTooShort { min: 8, found: 5 })
+        );
+
+        // A salt that is too short fails at once, without retries.
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let mut calls = 0;
+        let verified =
+            hash.verify_with_retry_using("password123", 5, |_, pw| {
+                calls += 1;
+                let mut output = [0u8; 32];
+                Argon2i::hash_with_params(
+                    pw.as_bytes(),
+                    b"short",
+                    0x13,
+                    4096,
+                    3,
+                    1,
+                    &mut output,
+                )
+                .map(|()| true)
+            });
+        assert!(matches!(verified, Err(HshError::SaltTooShort { .. })));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_verify_and_migrate() {
+        let old = Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            10,
+            8,
+            1,
+            64,
+        )
+        .unwrap();
+        let target = HashOptions::new(
+            HashAlgorithm::Scrypt,
+            Params::Scrypt {
+                log_n: 11,
+                r: 8,
+                p: 1,
+            },
+        )
+        .unwrap();
+
+        let (verified, migrated) =
+            old.verify_and_migrate("password123", &target).unwrap();
+        assert!(verified);
+        let migrated = migrated.unwrap();
+        assert_eq!(migrated.scheme(), target);
+        assert_ne!(migrated.salt(), old.salt());
+        assert_eq!(migrated.verify("password123"), Ok(true));
+
+        // Up to date: nothing to migrate.
+        assert_eq!(
+            migrated.verify_and_migrate("password123", &target),
+            Ok((true, None))
+        );
+        // Wrong password: never migrated.
+        assert_eq!(
+            old.verify_and_migrate("password456", &target),
+            Ok((false, None))
+        );
+    }
+
+    #[test]
+    fn test_verify_and_migrate_digest_length() {
+        let old = Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            10,
+            8,
+            1,
+            32,
+        )
+        .unwrap();
+
+        // Same algorithm: the digest length is kept.
+        let target = HashOptions::new(
+            HashAlgorithm::Scrypt,
+            Params::Scrypt {
+                log_n: 11,
+                r: 8,
+                p: 1,
+            },
+        )
+        .unwrap();
+        let (_, migrated) =
+            old.verify_and_migrate("password123", &target).unwrap();
+        let migrated = migrated.unwrap();
+        assert_eq!(migrated.hash_length(), 32);
+        assert_eq!(migrated.verify("password123"), Ok(true));
+
+        // Another algorithm: its default digest length is used.
+        let target = HashOptions::new(
+            HashAlgorithm::Scrypt,
+            Params::default_for(HashAlgorithm::Scrypt),
+        )
+        .unwrap();
+        let old = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x13,
+            256,
+            2,
+            1,
+            24,
+        )
+        .unwrap();
+        let (_, migrated) =
+            old.verify_and_migrate("password123", &target).unwrap();
+        let migrated = migrated.unwrap();
+        assert_eq!(migrated.hash_length(), 64);
+        assert_eq!(migrated.verify("password123"), Ok(true));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        assert!(hash.ct_eq(&hash.clone()));
+        assert!(hash.ct_eq(
+            &Hash::new("password123", "somesalt", "argon2i").unwrap()
+        ));
+
+        // Another password, salt or algorithm.
+        for other in [
+            Hash::new("password456", "somesalt", "argon2i").unwrap(),
+            Hash::new("password123", "othersalt", "argon2i").unwrap(),
+            Hash::new("password123", "somesalt", "argon2id").unwrap(),
+        ] {
+            assert!(!hash.ct_eq(&other));
+            assert!(!other.ct_eq(&hash));
+        }
+    }
+
+    #[test]
+    fn test_rotate_salt_on_verify() {
+        for hash in [
+            Hash::new("password123", "somesalt", "argon2id").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ] {
+            let rotated = hash
+                .rotate_salt_on_verify("password123")
+                .unwrap()
+                .unwrap();
+            assert_eq!(rotated.scheme(), hash.scheme());
+            assert_ne!(rotated.salt(), hash.salt());
+            assert_eq!(rotated.verify("password123"), Ok(true));
+
+            // Wrong password: never rotated.
+            assert_eq!(
+                hash.rotate_salt_on_verify("password456"),
+                Ok(None)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotate_salt_on_verify_keeps_digest_length() {
+        for hash in [
+            Hash::new_argon2i_with_params(
+                "password123",
+                b"somesalt".to_vec(),
+                0x13,
+                256,
+                2,
+                1,
+                24,
+            )
+            .unwrap(),
+            Hash::new_scrypt_with_params(
+                "password123",
+                b"somesalt".to_vec(),
+                10,
+                8,
+                1,
+                32,
+            )
+            .unwrap(),
+        ] {
+            let rotated = hash
+                .rotate_salt_on_verify("password123")
+                .unwrap()
+                .unwrap();
+            assert_eq!(rotated.hash_length(), hash.hash_length());
+            assert_eq!(rotated.verify("password123"), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_serialized_bcrypt_hash_has_no_salt() {
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        let json = serde_json::to_value(&hash).unwrap();
+        assert!(json.get("salt").is_none());
+
+        let parsed: Hash = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, hash);
+        assert_eq!(parsed.salt(), hash.salt());
+        assert_eq!(parsed.verify("password123"), Ok(true));
+
+        // Other algorithms keep their salt.
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        let json = serde_json::to_value(&hash).unwrap();
+        assert_eq!(json["salt"], serde_json::json!(b"somesalt"));
+    }
+
+    #[test]
+    fn test_deserialize_v0_json_applies_legacy_params() {
+        // Earlier releases wrote no version and no parameters, and
+        // hashed Argon2i with version 0x10.
+        let legacy = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x10,
+            4096,
+            3,
+            1,
+            32,
+        )
+        .unwrap();
+        let v0 = serde_json::json!({
+            "hash": legacy.hash,
+            "salt": legacy.salt,
+            "algorithm": "Argon2i",
+        });
+
+        let hash: Hash = serde_json::from_value(v0).unwrap();
+        assert_eq!(
+            hash.params,
+            Some(Params::legacy(HashAlgorithm::Argon2i))
+        );
+        assert_eq!(hash, legacy);
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        // Current data carries its version and is read as is.
+        let json = serde_json::to_value(&hash).unwrap();
+        assert_eq!(json["version"], hsh::models::hash::SERDE_VERSION);
+        assert_eq!(serde_json::from_value::<Hash>(json).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_newer_version() {
+        let json = r#"{"version":99,"hash":[1,2,3],"salt":[],"algorithm":"Argon2i"}"#;
+        let err = serde_json::from_str::<Hash>(json).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("unknown serialization version 99"));
+    }
+
+    #[test]
+    fn test_verify_expecting_rejects_other_algorithms() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+
+        let err = hash
+            .verify_expecting("password123", HashAlgorithm::Bcrypt)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            HshError::AlgorithmMismatch {
+                expected: HashAlgorithm::Bcrypt,
+                found: HashAlgorithm::Argon2i,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Expected a Bcrypt hash, found Argon2i"
+        );
+
+        assert_eq!(
+            hash.verify_expecting(
+                "password123",
+                HashAlgorithm::Argon2i
+            ),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_verify_with_min_params_rejects_weak_strings() {
+        let policy = Params::default_for(HashAlgorithm::Argon2i);
+
+        // A cheap hash of a known password, as an attacker would plant.
+        let weak = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x13,
+            8,
+            1,
+            1,
+            32,
+        )
+        .unwrap()
+        .to_phc_string()
+        .unwrap();
+        let err =
+            Hash::verify_with_min_params(&weak, "password123", &policy)
+                .unwrap_err();
+        assert!(matches!(err, HshError::ParamsBelowPolicy(_)));
+        assert!(err.to_string().starts_with("Parameters below policy"));
+
+        // Parameters of another algorithm never meet the policy.
+        let scrypt = Hash::new("password123", "somesalt", "scrypt")
+            .unwrap()
+            .to_phc_string()
+            .unwrap();
+        assert!(matches!(
+            Hash::verify_with_min_params(
+                &scrypt,
+                "password123",
+                &policy
+            ),
+            Err(HshError::ParamsBelowPolicy(_))
+        ));
+
+        let strong = Hash::new("password123", "somesalt", "argon2i")
+            .unwrap()
+            .to_phc_string()
+            .unwrap();
+        assert_eq!(
+            Hash::verify_with_min_params(
+                &strong,
+                "password123",
+                &policy
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            Hash::verify_with_min_params(
+                &strong,
+                "password456",
+                &policy
+            ),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_verify_with_prehash_fn() {
+        // Simulates an HSM computing HMAC-SHA256 with a pepper it holds.
+        let hsm = |password: &[u8]| {
+            let prehash = Prehash::HmacSha256 {
+                key: b"pepper".to_vec(),
+            };
+            prehash
+                .apply(std::str::from_utf8(password).unwrap())
+                .unwrap()
+                .into_bytes()
+        };
+        let stored = String::from_utf8(hsm(b"password123")).unwrap();
+        let hash = Hash::new(&stored, "somesalt", "argon2i").unwrap();
+
+        assert_eq!(
+            hash.verify_with_prehash_fn("password123", hsm),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_with_prehash_fn("password456", hsm),
+            Ok(false)
+        );
+        assert_eq!(hash.verify("password123"), Ok(false));
+    }
+
+    #[test]
+    fn test_describe() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+        assert_eq!(
+            hash.describe(),
+            "argon2i v=19 m=4MiB t=3 p=1 salt=8B digest=32B"
+        );
+
+        let hash = Hash::new_argon2i_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            0x10,
+            1000,
+            2,
+            1,
+            16,
+        )
+        .unwrap();
+        assert_eq!(
+            hash.describe(),
+            "argon2i v=16 m=1000KiB t=2 p=1 salt=8B digest=16B"
+        );
+
+        let hash = Hash::new_bcrypt("password123", 4).unwrap();
+        assert_eq!(
+            hash.describe(),
+            "bcrypt cost=4 salt=16B digest=60B"
+        );
+    }
+
+    #[test]
+    fn test_new_with_algorithm() {
+        for algorithm in [
+            HashAlgorithm::Argon2i,
+            HashAlgorithm::Bcrypt,
+            HashAlgorithm::Scrypt,
+        ] {
+            let hash = Hash::new_with_algorithm(
+                "password123",
+                "somesalt",
+                algorithm,
+            )
+            .unwrap();
+            assert_eq!(hash.algorithm, algorithm);
+            assert_eq!(hash.verify("password123"), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_verify_salts_ct() {
+        let hash =
+            Hash::new("password123", "thirdsalt", "argon2i").unwrap();
+        let candidates: [&[u8]; 3] =
+            [b"firstsalt", b"secondsalt", b"thirdsalt"];
+
+        assert_eq!(
+            hash.verify_salts_ct("password123", &candidates),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_salts_ct("password456", &candidates),
+            Ok(false)
+        );
+        assert_eq!(
+            hash.verify_salts_ct("password123", &candidates[..2]),
+            Ok(false)
+        );
+
+        let bcrypt = Hash::new_bcrypt("password123", 4).unwrap();
+        assert!(matches!(
+            bcrypt.verify_salts_ct("password123", &candidates),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_argon2i_delegates_to_params() {
+        let salt = b"somesalt".to_vec();
+        let hash =
+            Hash::new_argon2i("password123", salt.clone()).unwrap();
+        let explicit = Hash::new_argon2i_with_params(
+            "password123",
+            salt.clone(),
+            0x13,
+            4096,
+            3,
+            1,
+            32,
+        )
+        .unwrap();
+
+        assert_eq!(hash, explicit);
+        assert_eq!(
+            hash.parameters(),
+            Params::default_for(HashAlgorithm::Argon2i)
+        );
+        assert!(Hash::new_argon2i_with_params(
+            "password123",
+            salt,
+            0x13,
+            4096,
+            0,
+            1,
+            32
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_reader() {
+        let hash =
+            Hash::new("password123", "somesalt", "argon2i").unwrap();
+
+        assert_eq!(
+            hash.verify_reader(std::io::Cursor::new(b"password123")),
+            Ok(true)
+        );
+        assert_eq!(
+            hash.verify_reader(std::io::Cursor::new(b"password123\n")),
+            Ok(false)
+        );
+
+        let long = vec![b'a'; hsh::MAX_PASSWORD_LEN + 1];
+        assert!(matches!(
+            hash.verify_reader(long.as_slice()),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_from_reader_matches_new() {
+        use std::io::{Cursor, Read};
+
+        for algo in ["argon2i", "argon2id", "scrypt"] {
+            let hash = Hash::new_from_reader(
+                Cursor::new("password123"),
+                "somesalt",
+                algo,
+            )
+            .unwrap();
+            assert_eq!(
+                hash,
+                Hash::new("password123", "somesalt", algo).unwrap()
+            );
+        }
+
+        // Input arriving in chunks is hashed as a whole.
+        let chunks = Cursor::new("pass").chain(Cursor::new("word123"));
+        let hash = Hash::new_from_reader(chunks, "somesalt", "argon2i")
+            .unwrap();
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        // Bcrypt generates its own salt.
+        let hash = Hash::new_from_reader(
+            Cursor::new("password123"),
+            "",
+            "bcrypt",
+        )
+        .unwrap();
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        let long = vec![b'a'; hsh::MAX_PASSWORD_LEN + 1];
+        assert!(matches!(
+            Hash::new_from_reader(
+                long.as_slice(),
+                "somesalt",
+                "argon2i"
+            ),
+            Err(HshError::InvalidParams(_))
+        ));
+        assert_eq!(
+            Hash::new_from_reader(
+                Cursor::new("password123"),
+                "somesalt",
+                "md5"
+            ),
+            Err(HshError::UnsupportedAlgorithm(String::from("md5")))
+        );
+    }
+
+    #[test]
+    fn test_generate_salt_bytes() {
+        for (algo, len) in [
+            ("argon2i", 16),
+            ("argon2id", 16),
+            ("bcrypt", 16),
+            ("scrypt", 32),
+        ] {
+            let salt = Hash::generate_salt_bytes(algo).unwrap();
+            assert_eq!(salt.len(), len, "{}", algo);
+            assert_ne!(salt, Hash::generate_salt_bytes(algo).unwrap());
+        }
+        assert_eq!(
+            Hash::generate_salt_bytes("md5"),
+            Err(HshError::UnsupportedAlgorithm(String::from("md5")))
+        );
+    }
+
+    #[test]
+    fn test_generated_scrypt_salt_is_used_consistently() {
+        use base64::Engine as _;
+
+        let salt = Hash::generate_salt("scrypt").unwrap();
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(&salt)
+            .unwrap();
+        assert_eq!(decoded.len(), 32);
+
+        // Decoded at hash time, the salt is stored and verified as its
+        // raw bytes.
+        let hash = Hash::new_with_salt_encoding(
+            "password123",
+            &salt,
+            "scrypt",
+            SaltEncoding::Base64,
+        )
+        .unwrap();
+        assert_eq!(hash.salt(), decoded.as_slice());
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(
+            hash,
+            Hash::new_bytes(
+                b"password123",
+                decoded,
+                HashAlgorithm::Scrypt
+            )
+            .unwrap()
+        );
+
+        // Used as is, the salt is stored and verified as its text.
+        let hash = Hash::new("password123", &salt, "scrypt").unwrap();
+        assert_eq!(hash.salt(), salt.as_bytes());
+        assert_eq!(hash.verify("password123"), Ok(true));
+
+        assert!(matches!(
+            Hash::new_with_salt_encoding(
+                "password123",
+                "not base64!",
+                "scrypt",
+                SaltEncoding::Base64
+            ),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_salt_encoding_is_not_interpreted() {
+        // A base64 Scrypt salt works unchanged on the Argon2i path.
+        let salt = Hash::generate_salt("scrypt").unwrap();
+        assert!(salt.ends_with('='));
+
+        let hash = Hash::new("password123", &salt, "argon2i").unwrap();
+        assert_eq!(hash.salt(), salt.as_bytes());
+        assert_eq!(hash.verify("password123"), Ok(true));
+    }
 }
@@ -1,10 +1,17 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the `HashAlgorithm` enum and `HashingAlgorithm` trait.
+
 #[cfg(test)]
 mod tests {
-    use hsh::models::hash_algorithm::{
-        HashAlgorithm, HashingAlgorithm,
+    use hsh::models::{
+        error::HshError,
+        hash::Hash,
+        hash_algorithm::{
+            HashAlgorithm, HashingAlgorithm, SecurityLevel,
+            VerifyMethod,
+        },
     };
 
     // Dummy struct to implement HashingAlgorithm for testing
@@ -30,6 +37,33 @@ mod tests {
         assert_eq!(scrypt as i32, 2);
     }
 
+    #[test]
+    fn test_discriminants_are_stable() {
+        // Stored data relies on these values; new algorithms append.
+        let hashes = [
+            (
+                Hash::new("password123", "somesalt", "argon2i")
+                    .unwrap(),
+                0,
+            ),
+            (Hash::new_bcrypt("password123", 4).unwrap(), 1),
+            (
+                Hash::new("password123", "somesalt", "scrypt").unwrap(),
+                2,
+            ),
+            (
+                Hash::new("password123", "somesalt", "argon2id")
+                    .unwrap(),
+                3,
+            ),
+        ];
+        for (hash, discriminant) in hashes {
+            assert_eq!(hash.algorithm as u8, discriminant);
+            // The second byte of the binary layout is the discriminant.
+            assert_eq!(hash.to_bytes().unwrap()[1], discriminant);
+        }
+    }
+
     #[test]
     fn test_hashing_algorithm_trait() {
         let password = "password123";
@@ -38,4 +72,145 @@ mod tests {
             DummyAlgorithm::hash_password(password, salt).unwrap();
         assert_eq!(hashed, vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_hash_algorithm_serde_round_trip() {
+        for algorithm in [
+            HashAlgorithm::Argon2i,
+            HashAlgorithm::Bcrypt,
+            HashAlgorithm::Scrypt,
+        ] {
+            let json = serde_json::to_string(&algorithm).unwrap();
+            let parsed: HashAlgorithm =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, algorithm);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_unknown_algorithm() {
+        let json = r#"{"hash":[1,2,3],"salt":[],"algorithm":"md5"}"#;
+        let err = serde_json::from_str::<Hash>(json).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Unsupported hash algorithm: md5"));
+    }
+
+    #[test]
+    fn test_from_phc_tag() {
+        assert_eq!(
+            HashAlgorithm::from_phc_tag("argon2i"),
+            Ok(HashAlgorithm::Argon2i)
+        );
+        assert_eq!(
+            HashAlgorithm::from_phc_tag("scrypt"),
+            Ok(HashAlgorithm::Scrypt)
+        );
+        assert_eq!(
+            HashAlgorithm::from_phc_tag("argon2id"),
+            Ok(HashAlgorithm::Argon2id)
+        );
+        for tag in ["2a", "2b", "2x", "2y"] {
+            assert_eq!(
+                HashAlgorithm::from_phc_tag(tag),
+                Ok(HashAlgorithm::Bcrypt)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_phc_tag_rejects_names_and_unknown_tags() {
+        for tag in ["bcrypt", "Argon2i", "argon2d", "2", ""] {
+            assert_eq!(
+                HashAlgorithm::from_phc_tag(tag),
+                Err(HshError::UnsupportedAlgorithm(tag.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_security_level() {
+        assert_eq!(
+            HashAlgorithm::Argon2i.security_level(),
+            SecurityLevel::Recommended
+        );
+        assert_eq!(
+            HashAlgorithm::Bcrypt.security_level(),
+            SecurityLevel::Acceptable
+        );
+        assert_eq!(
+            HashAlgorithm::Scrypt.security_level(),
+            SecurityLevel::Recommended
+        );
+    }
+
+    #[test]
+    fn test_verify_method_and_verification() {
+        assert_eq!(
+            HashAlgorithm::Argon2i.verify_method(),
+            VerifyMethod::Recompute
+        );
+        assert_eq!(
+            HashAlgorithm::Bcrypt.verify_method(),
+            VerifyMethod::Backend
+        );
+        assert_eq!(
+            HashAlgorithm::Scrypt.verify_method(),
+            VerifyMethod::Recompute
+        );
+
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        for hash in hashes {
+            assert_eq!(hash.verify("password123"), Ok(true));
+            assert_eq!(hash.verify("password456"), Ok(false));
+        }
+    }
+
+    #[test]
+    fn test_minimums_are_enforced_by_constructors() {
+        assert_eq!(HashAlgorithm::Argon2i.min_salt_len(), 8);
+        assert_eq!(HashAlgorithm::Bcrypt.min_salt_len(), 0);
+        assert_eq!(HashAlgorithm::Scrypt.min_salt_len(), 8);
+        for algorithm in [
+            HashAlgorithm::Argon2i,
+            HashAlgorithm::Bcrypt,
+            HashAlgorithm::Scrypt,
+        ] {
+            assert_eq!(
+                algorithm.min_password_len(),
+                hsh::MIN_PASSWORD_LEN
+            );
+        }
+        assert_eq!(hsh::MIN_PASSWORD_LEN, 8);
+
+        // Passwords one byte short of the minimum.
+        assert!(Hash::new("passwor", "somesalt", "scrypt").is_err());
+        assert!(Hash::new_bcrypt("passwor", 4).is_err());
+        assert!(
+            Hash::new_argon2i("passwor", b"somesalt".to_vec()).is_err()
+        );
+        assert!(matches!(
+            Hash::new_bytes(
+                b"passwor",
+                b"somesalt".to_vec(),
+                HashAlgorithm::Argon2i
+            ),
+            Err(HshError::PasswordTooShort { min: 8 })
+        ));
+
+        // Salts one byte short of the minimum.
+        assert!(Hash::new("password123", "somesal", "argon2i").is_err());
+        assert!(Hash::new_scrypt_unchecked(
+            "password123",
+            b"somesal".to_vec()
+        )
+        .is_err());
+        assert!(Hash::new("password123", "", "bcrypt").is_ok());
+        assert!(Hash::new("password123", "somesalt", "scrypt").is_ok());
+    }
 }
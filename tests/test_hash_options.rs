@@ -0,0 +1,156 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the `HashOptions` struct.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::{
+        error::HshError,
+        hash_algorithm::HashAlgorithm,
+        hash_options::{
+            HashOptions, RECOMMENDED_MAX_M_COST, RECOMMENDED_MIN_M_COST,
+        },
+        params::Params,
+    };
+
+    #[test]
+    fn test_new_rejects_mismatched_params() {
+        let params = Params::default_for(HashAlgorithm::Scrypt);
+
+        assert!(HashOptions::new(HashAlgorithm::Scrypt, params).is_ok());
+        assert!(matches!(
+            HashOptions::new(HashAlgorithm::Bcrypt, params),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_estimated_memory_bytes_argon2() {
+        let options = HashOptions::new(
+            HashAlgorithm::Argon2i,
+            Params::Argon2 {
+                version: 0x13,
+                m_cost: 19 * 1024,
+                t_cost: 2,
+                p_cost: 1,
+            },
+        )
+        .unwrap();
+
+        // m_cost KiB of 1 KiB blocks.
+        assert_eq!(options.estimated_memory_bytes(), 19 * 1024 * 1024);
+
+        // Rounded down to a multiple of 4 * p_cost blocks.
+        let options = HashOptions::new(
+            HashAlgorithm::Argon2i,
+            Params::Argon2 {
+                version: 0x13,
+                m_cost: 4099,
+                t_cost: 2,
+                p_cost: 4,
+            },
+        )
+        .unwrap();
+        assert_eq!(options.estimated_memory_bytes(), 4096 * 1024);
+    }
+
+    #[test]
+    fn test_estimated_memory_bytes_scrypt() {
+        let options = HashOptions::default_for(HashAlgorithm::Scrypt);
+
+        // 128 * r * N + 128 * r * p, with N = 2^14, r = 8 and p = 1.
+        assert_eq!(
+            options.estimated_memory_bytes(),
+            128 * 8 * (1 << 14) + 128 * 8
+        );
+
+        let options = HashOptions {
+            algorithm: HashAlgorithm::Scrypt,
+            params: Params::Scrypt {
+                log_n: 200,
+                r: 8,
+                p: 1,
+            },
+        };
+        assert_eq!(options.estimated_memory_bytes(), u64::MAX);
+    }
+
+    #[test]
+    fn test_is_expensive() {
+        let high_memory = HashOptions::new(
+            HashAlgorithm::Argon2i,
+            Params::Argon2 {
+                version: 0x13,
+                m_cost: 64 * 1024,
+                t_cost: 3,
+                p_cost: 1,
+            },
+        )
+        .unwrap();
+        assert!(high_memory.is_expensive());
+        assert!(HashOptions::default_for(HashAlgorithm::Scrypt)
+            .is_expensive());
+
+        let cheap_bcrypt = HashOptions::new(
+            HashAlgorithm::Bcrypt,
+            Params::Bcrypt { cost: 4 },
+        )
+        .unwrap();
+        assert!(!cheap_bcrypt.is_expensive());
+        assert!(HashOptions::default_for(HashAlgorithm::Bcrypt)
+            .is_expensive());
+    }
+
+    #[test]
+    fn test_recommended_for_memory_is_clamped() {
+        let m_cost =
+            |bytes| match HashOptions::recommended_for_memory(bytes)
+                .params
+            {
+                Params::Argon2 { m_cost, .. } => m_cost,
+                other => panic!("unexpected parameters: {:?}", other),
+            };
+
+        assert_eq!(m_cost(0), RECOMMENDED_MIN_M_COST);
+        assert_eq!(m_cost(1 << 30), 52_428);
+        assert_eq!(m_cost(u64::MAX), RECOMMENDED_MAX_M_COST);
+
+        let options = HashOptions::recommended_for_memory(u64::MAX);
+        assert_eq!(options.algorithm, HashAlgorithm::Argon2i);
+        assert!(options.params.check_limits().is_ok());
+    }
+
+    #[test]
+    fn test_relative_strength_across_algorithms() {
+        let argon2 = HashOptions::new(
+            HashAlgorithm::Argon2i,
+            Params::Argon2 {
+                version: 0x13,
+                m_cost: 64 * 1024,
+                t_cost: 3,
+                p_cost: 1,
+            },
+        )
+        .unwrap();
+        let bcrypt = HashOptions::new(
+            HashAlgorithm::Bcrypt,
+            Params::Bcrypt { cost: 10 },
+        )
+        .unwrap();
+
+        assert!(
+            argon2.relative_strength() > bcrypt.relative_strength()
+        );
+
+        // One more Bcrypt cost doubles the work, adding one to the score.
+        let stronger = HashOptions::new(
+            HashAlgorithm::Bcrypt,
+            Params::Bcrypt { cost: 11 },
+        )
+        .unwrap();
+        let difference =
+            stronger.relative_strength() - bcrypt.relative_strength();
+        assert!((difference - 1.0).abs() < 1e-9);
+    }
+}
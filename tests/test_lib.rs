@@ -1,10 +1,15 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the public API of the Hash (HSH) library.
+
 #[cfg(test)]
 mod tests {
+    use hsh::models::error::HshError;
     use hsh::models::hash::Hash;
     use hsh::models::hash_algorithm::HashAlgorithm;
+    use hsh::models::params::Params;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha8Rng};
 
     #[test]
     fn test_new() {
@@ -32,7 +37,7 @@ mod tests {
             }
             Err(e) => {
                 assert_eq!(
-                    e,
+                    e.to_string(),
                     format!("Unsupported hash algorithm: {}", algo)
                 );
             }
@@ -51,6 +56,38 @@ mod tests {
         assert!(!hash.verify("wrongpassword").unwrap());
     }
 
+    #[test]
+    fn test_verify_argon2i_and_scrypt_digests() {
+        for algo in ["argon2i", "scrypt"] {
+            let hash =
+                Hash::new("password123", "somesalt", algo).unwrap();
+            assert_eq!(
+                hash.verify("password123"),
+                Ok(true),
+                "{}",
+                algo
+            );
+            assert_eq!(
+                hash.verify("password124"),
+                Ok(false),
+                "{}",
+                algo
+            );
+
+            // A digest differing only in its last byte must not match.
+            let mut near_miss = hash.clone();
+            let mut digest = hash.hash().to_vec();
+            *digest.last_mut().unwrap() ^= 1;
+            near_miss.set_hash(&digest).unwrap();
+            assert_eq!(
+                near_miss.verify("password123"),
+                Ok(false),
+                "{}",
+                algo
+            );
+        }
+    }
+
     #[test]
     fn test_from_string() {
         // You'll need to provide a valid hash string here for this test
@@ -69,6 +106,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_string_then_verify() {
+        let hash_string = "$argon2i$v=19$m=4096,t=3,p=1$c29tZXNhbHQ$KD1n75Vc+TtHe2tF4fk0o29p/EEdGzCpCnXbBsn9rSY";
+
+        let hash = Hash::from_string(hash_string).unwrap();
+
+        // The salt is the decoded salt segment, not the parameters.
+        assert_eq!(hash.salt, b"somesalt".to_vec());
+        assert!(hash.verify("password123").unwrap());
+        assert!(!hash.verify("wrongpassword").unwrap());
+    }
+
     #[test]
     fn test_from_string_invalid_hash_string() {
         // Provide an invalid hash string
@@ -82,7 +131,7 @@ mod tests {
         // Check the error message
         match hash {
             Err(e) => {
-                assert_eq!(e, String::from("Invalid hash string"))
+                assert_eq!(e.to_string(), "Invalid hash string")
             }
             _ => panic!("Expected Err, got Ok"),
         }
@@ -110,7 +159,7 @@ mod tests {
         // Check the error message
         match salt {
             Err(e) => assert_eq!(
-                e,
+                e.to_string(),
                 format!("Unsupported hash algorithm: {}", invalid_algo)
             ),
             _ => panic!("Expected Err, got Ok"),
@@ -127,6 +176,18 @@ mod tests {
         assert_eq!(salt.len(), 24); // bcrypt salt will be longer due to base64 encoding
     }
 
+    #[test]
+    fn test_generate_salts_are_distinct() {
+        let salts = Hash::generate_salts("argon2i", 10_000).unwrap();
+        assert_eq!(salts.len(), 10_000);
+
+        let unique: std::collections::HashSet<&String> =
+            salts.iter().collect();
+        assert_eq!(unique.len(), salts.len());
+
+        assert!(Hash::generate_salts("md5", 1).is_err());
+    }
+
     #[test]
     fn test_generate_salt_scrypt() {
         let algo = "scrypt";
@@ -137,6 +198,29 @@ mod tests {
         assert_eq!(salt.len(), 44); // scrypt salt will be longer due to base64 encoding
     }
 
+    #[test]
+    fn test_generate_salt_with_seeded_rng() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let salt =
+            Hash::generate_salt_with_rng("scrypt", &mut rng).unwrap();
+        assert_eq!(
+            salt,
+            "oVtdObW/kK6IkXklxj9F84xTtsUIt3FtUmcWWPmymqA="
+        );
+
+        // The same seed gives the same salt.
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        assert_eq!(
+            Hash::generate_salt_with_rng("scrypt", &mut rng).unwrap(),
+            salt
+        );
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let salt =
+            Hash::generate_salt_with_rng("argon2i", &mut rng).unwrap();
+        assert_eq!(salt, "L5mmqiu0EIDubina");
+    }
+
     #[test]
     fn test_argon2i_hashing() {
         let password = "password123";
@@ -188,6 +272,40 @@ mod tests {
         assert!(!hash.verify(password).unwrap());
     }
 
+    #[test]
+    fn test_set_password_keeps_params() {
+        let password = "newpassword123";
+
+        // Non-default parameters and digest length.
+        let mut hash = Hash::new_scrypt_with_params(
+            "password123",
+            b"somesalt".to_vec(),
+            10,
+            8,
+            1,
+            32,
+        )
+        .unwrap();
+        let params = hash.parameters();
+        hash.set_password(password, "othersalt", "scrypt").unwrap();
+        assert_eq!(hash.parameters(), params);
+        assert_eq!(hash.hash_length(), 32);
+        assert_eq!(hash.salt(), b"othersalt");
+        assert_eq!(hash.verify(password), Ok(true));
+
+        // A legacy Argon2i hash, stored without parameters (v0x10).
+        let phc = "$argon2i$v=16$m=4096,t=3,p=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+        let mut hash = Hash::from_string(phc).unwrap();
+        hash.params = None;
+        hash.set_password(password, "othersalt", "argon2i").unwrap();
+        assert_eq!(
+            hash.parameters(),
+            Params::legacy(HashAlgorithm::Argon2i)
+        );
+        assert_eq!(hash.verify(password), Ok(true));
+        assert_eq!(hash.verify("password123"), Ok(false));
+    }
+
     #[test]
     fn test_invalid_algorithm() {
         let password = "password123";
@@ -231,7 +349,7 @@ mod tests {
         let original_hash = Hash::new(password, salt, algo).unwrap();
 
         // Get the hashed password bytes
-        let hashed_password = original_hash.hash;
+        let hashed_password = original_hash.hash.clone();
 
         // Now try to create a new Hash struct from the hashed password bytes
         let from_hash = Hash::from_hash(&hashed_password, algo);
@@ -263,7 +381,7 @@ mod tests {
             Hash::new(password, salt, "bcrypt").unwrap();
 
         // Get the hashed password bytes
-        let hashed_password = original_hash.hash;
+        let hashed_password = original_hash.hash.clone();
 
         // Now try to create a new Hash struct from the hashed password bytes
         let from_hash = Hash::from_hash(&hashed_password, algo);
@@ -274,7 +392,7 @@ mod tests {
         // Check the error message
         match from_hash {
             Err(e) => assert_eq!(
-                e,
+                e.to_string(),
                 format!("Unsupported hash algorithm: {}", algo)
             ),
             _ => panic!("Expected Err, got Ok"),
@@ -306,57 +424,130 @@ mod tests {
         // Create a new Hash
         let original_hash = Hash::new(password, salt, algo).unwrap();
 
-        // Convert the salt to bytes for comparison
-        let salt_bytes = salt.as_bytes();
-
-        // Test the `salt` method
-        assert_eq!(original_hash.salt(), salt_bytes);
+        // Bcrypt ignores the given salt: the `salt` method returns the
+        // 16 bytes embedded in the hash instead
+        assert_eq!(original_hash.salt().len(), 16);
+        assert_ne!(original_hash.salt(), salt.as_bytes());
     }
 
     #[test]
     fn test_set_hash() {
         let password = "password123";
         let salt = "somesalt";
-        let algo = "bcrypt";
+        let algo = "argon2i";
 
         // Create a new Hash
         let mut original_hash =
             Hash::new(password, salt, algo).unwrap();
+        let original_digest = original_hash.hash().to_vec();
 
-        // Create a new hash value
-        let new_hash = vec![1, 2, 3, 4, 5];
+        // A 5-byte digest cannot come from Argon2i and is rejected,
+        // leaving the hash as is
+        assert!(matches!(
+            original_hash.set_hash(&[1, 2, 3, 4, 5]),
+            Err(HshError::InvalidParams(_))
+        ));
+        assert_eq!(original_hash.hash(), &original_digest);
+
+        // Create a new hash value of a valid length
+        let new_hash = vec![7; 32];
 
         // Set the hash of the Hash struct to the new value
-        original_hash.set_hash(&new_hash);
+        original_hash.set_hash(&new_hash).unwrap();
 
         // Test that the `hash` method returns the new hash value
         assert_eq!(original_hash.hash(), &new_hash);
+
+        // Bcrypt hashes must be a whole modular crypt string
+        let mut bcrypt = Hash::new_bcrypt(password, 4).unwrap();
+        assert_eq!(
+            bcrypt.set_hash(&[1, 2, 3, 4, 5]),
+            Err(HshError::InvalidHashString)
+        );
+        let other = Hash::new_bcrypt(password, 4).unwrap();
+        bcrypt.set_hash(other.hash()).unwrap();
+        assert_eq!(bcrypt.verify(password), Ok(true));
     }
 
     #[test]
     fn test_set_salt() {
         let password = "password123";
         let salt = "somesalt";
-        let algo = "bcrypt";
+        let algo = "argon2i";
 
         // Create a new Hash
         let mut original_hash =
             Hash::new(password, salt, algo).unwrap();
+        let original_digest = original_hash.hash().to_vec();
 
         // Create a new salt value
-        let new_salt = vec![1, 2, 3, 4, 5];
+        let new_salt = vec![1, 2, 3, 4, 5, 6, 7, 8];
 
         // Set the salt of the Hash struct to the new value
-        original_hash.set_salt(&new_salt);
+        original_hash.set_salt(password, &new_salt).unwrap();
 
-        // Test that the `salt` method returns the new salt value
+        // Test that the `salt` method returns the new salt value, and
+        // that the digest was recomputed with it
         assert_eq!(original_hash.salt(), &new_salt);
+        assert_ne!(original_hash.hash(), &original_digest);
+        assert_eq!(original_hash.verify(password), Ok(true));
+
+        // A salt that is too short is rejected, leaving the hash as is
+        assert!(original_hash.set_salt(password, &[1, 2, 3]).is_err());
+        assert_eq!(original_hash.salt(), &new_salt);
+        assert_eq!(original_hash.verify(password), Ok(true));
+    }
+
+    #[test]
+    fn test_set_salt_rejects_wrong_password() {
+        let password = "password123";
+        let mut hash =
+            Hash::new(password, "somesalt", "argon2i").unwrap();
+        let original = hash.clone();
+
+        assert_eq!(
+            hash.set_salt("WRONGPASSWORD", b"othersalt"),
+            Err(HshError::PasswordMismatch)
+        );
+        assert_eq!(hash, original);
+        assert_eq!(hash.verify(password), Ok(true));
+    }
+
+    #[test]
+    fn test_set_salt_keeps_digest_length() {
+        let password = "password123";
+        let mut argon2i = Hash::new_argon2i_with_params(
+            password,
+            b"somesalt".to_vec(),
+            0x13,
+            256,
+            2,
+            1,
+            24,
+        )
+        .unwrap();
+        argon2i.set_salt(password, b"othersalt").unwrap();
+        assert_eq!(argon2i.hash_length(), 24);
+        assert_eq!(argon2i.verify(password), Ok(true));
+
+        let mut scrypt = Hash::new_scrypt_with_params(
+            password,
+            b"somesalt".to_vec(),
+            10,
+            8,
+            1,
+            32,
+        )
+        .unwrap();
+        scrypt.set_salt(password, b"othersalt").unwrap();
+        assert_eq!(scrypt.hash_length(), 32);
+        assert_eq!(scrypt.verify(password), Ok(true));
     }
     #[test]
     fn test_to_string_representation() {
         let password = "password123";
         let salt = "somesalt";
-        let algo = "bcrypt";
+        let algo = "argon2i";
 
         // Create a new Hash
         let original_hash = Hash::new(password, salt, algo).unwrap();
@@ -480,7 +671,7 @@ mod tests {
 
         assert!(algorithm.is_err());
         assert_eq!(
-            algorithm.err().unwrap(),
+            algorithm.err().unwrap().to_string(),
             "Unsupported hash algorithm: unsupported"
         );
     }
@@ -491,6 +682,9 @@ mod tests {
         let algorithm = Hash::parse_algorithm(hash_str);
 
         assert!(algorithm.is_err());
-        assert_eq!(algorithm.err().unwrap(), "Invalid hash string");
+        assert_eq!(
+            algorithm.err().unwrap().to_string(),
+            "Invalid hash string"
+        );
     }
 }
@@ -0,0 +1,99 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the `login_verify` entry point.
+
+#[cfg(test)]
+mod tests {
+    use hsh::login_verify;
+    use hsh::models::error::HshError;
+
+    const ARGON2I_STORED: &str = "$argon2i$v=16$m=4096,t=3,p=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+    const BCRYPT_STORED: &str =
+        "$2b$04$KBCwKxOzLha2MUDgW0PjXeaTzotHTXw8StzE.taZVR2tApYiPTOmy";
+    const SCRYPT_STORED: &str = "$scrypt$ln=10,r=8,p=1$c29tZXNhbHQ$AgGjc1uwpRUm7ZMofHPj/fmCk9FDtbjURXhD+9G5788";
+
+    #[test]
+    fn test_login_verify_all_algorithms() {
+        for stored in [ARGON2I_STORED, BCRYPT_STORED, SCRYPT_STORED] {
+            assert_eq!(login_verify(stored, "password123"), Ok(true));
+            assert_eq!(
+                login_verify(stored, "wrongpassword"),
+                Ok(false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_login_verify_argon2i_requires_version() {
+        let stored = "$argon2i$m=4096,t=3,p=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+
+        assert_eq!(
+            login_verify(stored, "password123"),
+            Err(HshError::InvalidHashString)
+        );
+    }
+
+    #[test]
+    fn test_login_verify_argon2i_params_in_any_order() {
+        let stored = "$argon2i$v=16$p=1,t=3,m=4096$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+
+        assert_eq!(login_verify(stored, "password123"), Ok(true));
+    }
+
+    #[test]
+    fn test_login_verify_rejects_excessive_memory() {
+        let stored = "$argon2i$v=16$m=4194304,t=3,p=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo";
+
+        assert!(matches!(
+            login_verify(stored, "password123"),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_login_verify_rejects_excessive_scrypt_cost() {
+        let stored = "$scrypt$ln=30,r=8,p=1$c29tZXNhbHQ$AgGjc1uwpRUm7ZMofHPj/fmCk9FDtbjURXhD+9G5788";
+
+        assert!(matches!(
+            login_verify(stored, "password123"),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_login_verify_rejects_excessive_bcrypt_cost() {
+        let stored =
+            "$2b$31$KBCwKxOzLha2MUDgW0PjXeaTzotHTXw8StzE.taZVR2tApYiPTOmy";
+
+        assert!(matches!(
+            login_verify(stored, "password123"),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_login_verify_rejects_malformed_strings() {
+        for stored in [
+            "",
+            "password123",
+            "$argon2i$v=16$m=4096,t=3$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo",
+            "$argon2i$v=16$m=4096,t=3,p=1,x=1$c29tZXNhbHQ$LEC0RNWImGraw4dJ235vUSxQWLKGbBdTqVRhlhiDuHo",
+            "$scrypt$ln=10,r=8,p=1$c29tZXNhbHQ$not*base64",
+            "$2b$04$tooshort",
+            "$md5$rounds=1000$salt$hash",
+        ] {
+            assert!(login_verify(stored, "password123").is_err());
+        }
+    }
+
+    #[test]
+    fn test_login_verify_rejects_oversized_input() {
+        let stored = format!("{}{}", SCRYPT_STORED, "A".repeat(1024));
+
+        assert_eq!(
+            login_verify(&stored, "password123"),
+            Err(HshError::InvalidHashString)
+        );
+    }
+}
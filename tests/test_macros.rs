@@ -1,6 +1,8 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the macros of the Hash (HSH) library.
+
 #[cfg(test)]
 mod tests {
 
@@ -1,6 +1,8 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the `hsh` binary.
+
 #[cfg(test)]
 mod tests {
     use assert_cmd::prelude::*;
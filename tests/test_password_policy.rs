@@ -0,0 +1,101 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the `PasswordPolicy` struct.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::{
+        error::HshError, hash::Hash, password_policy::PasswordPolicy,
+    };
+
+    #[test]
+    fn test_default_policy() {
+        let policy = PasswordPolicy::default();
+        assert_eq!(policy, PasswordPolicy::new(hsh::MIN_PASSWORD_LEN));
+        assert_eq!(policy.max_len, None);
+
+        assert!(policy.check(b"password").is_ok());
+        assert_eq!(
+            policy.check(b"passwor"),
+            Err(HshError::PasswordTooShort { min: 8 })
+        );
+
+        // `new` uses the default policy.
+        assert_eq!(
+            Hash::new("passwor", "somesalt", "argon2i"),
+            Err(HshError::PasswordTooShort { min: 8 })
+        );
+        assert_eq!(
+            Hash::new("password", "somesalt", "argon2i").unwrap(),
+            Hash::new_with_policy(
+                "password",
+                "somesalt",
+                "argon2i",
+                PasswordPolicy::default()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        let policy = PasswordPolicy::new(12).with_max_len(16);
+
+        assert!(policy.check(b"password1234").is_ok());
+        assert!(policy.check(b"password12345678").is_ok());
+        assert_eq!(
+            policy.check(b"password123"),
+            Err(HshError::PasswordTooShort { min: 12 })
+        );
+        assert_eq!(
+            policy.check(b"password123456789"),
+            Err(HshError::PasswordTooLong { max: 16 })
+        );
+        assert_eq!(
+            HshError::PasswordTooShort { min: 12 }.to_string(),
+            "Password is too short. It must be at least 12 characters."
+        );
+    }
+
+    #[test]
+    fn test_new_with_policy() {
+        // A lower minimum accepts legacy short passwords.
+        let hash =
+            Hash::new_with_policy("abcd", "somesalt", "argon2i", 4)
+                .unwrap();
+        assert!(hash.verify("abcd").unwrap());
+        assert_eq!(
+            Hash::new_with_policy("abc", "somesalt", "argon2i", 4),
+            Err(HshError::PasswordTooShort { min: 4 })
+        );
+
+        // A higher minimum and a maximum are reported as configured.
+        let policy = PasswordPolicy::new(12).with_max_len(16);
+        assert_eq!(
+            Hash::new_with_policy(
+                "password123",
+                "somesalt",
+                "scrypt",
+                policy
+            ),
+            Err(HshError::PasswordTooShort { min: 12 })
+        );
+        assert_eq!(
+            Hash::new_with_policy(
+                "password123456789",
+                "somesalt",
+                "scrypt",
+                policy
+            ),
+            Err(HshError::PasswordTooLong { max: 16 })
+        );
+        assert!(Hash::new_with_policy(
+            "password1234",
+            "somesalt",
+            "scrypt",
+            policy
+        )
+        .is_ok());
+    }
+}
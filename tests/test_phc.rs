@@ -0,0 +1,115 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Property tests for the PHC string parser.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::error::HshError;
+    use hsh::phc;
+    use proptest::prelude::*;
+
+    const VALID: [&str; 3] = [
+        "$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$hi76pGBfHACvg0oijJpmmuz7/edPOmEOgIBVcQmLsIg",
+        "$scrypt$ln=10,r=8,p=1$c29tZXNhbHQ$AgGjc1uwpRUm7ZMofHPj/fmCk9FDtbjURXhD+9G5788",
+        "$2b$04$KBCwKxOzLha2MUDgW0PjXeaTzotHTXw8StzE.taZVR2tApYiPTOmy",
+    ];
+
+    #[test]
+    fn test_parse_encode_round_trip() {
+        for phc_str in VALID {
+            let hash = phc::parse(phc_str).unwrap();
+            assert_eq!(phc::encode(&hash).unwrap(), phc_str);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_segments() {
+        assert_eq!(
+            phc::parse("$argon2i$v=16$m=256,t=2,p=1$c29tZXNhbHQ$"),
+            Err(HshError::InvalidHashString)
+        );
+        assert_eq!(
+            phc::parse("$scrypt$ln=10,r=8,p=1$$AgGjc1uwpRUm7ZMofHPj"),
+            Err(HshError::InvalidHashString)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_control_characters() {
+        for phc_str in VALID {
+            let with_newline = format!("{}\n", phc_str);
+            let with_nul = phc_str.replacen('$', "$\0", 2);
+
+            assert_eq!(
+                phc::parse(&with_newline),
+                Err(HshError::InvalidHashString)
+            );
+            assert_eq!(
+                phc::parse(&with_nul),
+                Err(HshError::InvalidHashString)
+            );
+        }
+
+        let injected = "$scrypt$ln=10,r=8,p=1$c29tZXNhbHQ$AgGjc1uwpRUm7ZMofHPj/fmCk9FDtbjURXhD+9G5788\nINFO admin logged in";
+        assert_eq!(
+            hsh::models::hash::Hash::from_string(injected),
+            Err(HshError::InvalidHashString)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_never_panics(input in ".*") {
+            let _ = phc::parse(&input);
+        }
+
+        #[test]
+        fn test_parse_never_panics_on_dollar_segments(
+            segments in prop::collection::vec("[^$]{0,12}", 0..8)
+        ) {
+            let _ = phc::parse(&format!("${}", segments.join("$")));
+        }
+
+        #[test]
+        fn test_truncated_input_is_rejected(
+            index in 0usize..3,
+            cut in any::<prop::sample::Index>(),
+        ) {
+            // Cutting before the last `$` always removes a segment.
+            let valid = VALID[index];
+            let last = valid.rfind('$').unwrap();
+            let truncated = &valid[..cut.index(last + 1)];
+            prop_assert!(phc::parse(truncated).is_err());
+        }
+
+        #[test]
+        fn test_oversized_input_is_rejected(
+            index in 0usize..3,
+            suffix in "[A-Za-z0-9+/=$,]{1024,4096}",
+        ) {
+            let oversized = format!("{}{}", VALID[index], suffix);
+            // The input may still be well formed, but the digest is
+            // then too long to ever verify.
+            let verified = phc::parse(&oversized)
+                .and_then(|hash| hash.verify("password123"));
+            prop_assert!(verified.is_err());
+
+            let huge_param = format!("$argon2i$v=16$m={}$c29tZXNhbHQ$AAAA", suffix);
+            prop_assert!(phc::parse(&huge_param).is_err());
+        }
+
+        #[test]
+        fn test_non_ascii_input_is_rejected(
+            index in 0usize..3,
+            position in any::<prop::sample::Index>(),
+            insert in "[^\\x00-\\x7f]{1,4}",
+        ) {
+            let valid = VALID[index];
+            let at = position.index(valid.len() + 1);
+            let mutated =
+                format!("{}{}{}", &valid[..at], insert, &valid[at..]);
+            prop_assert!(phc::parse(&mutated).is_err());
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the warning logged when a password looks like a PHC
+//! string.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::hash::Hash;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    // Logger collecting warnings so the test can inspect them
+    struct CaptureLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl Log for CaptureLogger {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= Level::Warn
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            if self.enabled(record.metadata()) {
+                self.messages
+                    .lock()
+                    .unwrap()
+                    .push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_phc_looking_password_warns() {
+        let logger: &'static CaptureLogger =
+            Box::leak(Box::new(CaptureLogger {
+                messages: Mutex::new(Vec::new()),
+            }));
+        log::set_logger(logger).unwrap();
+        log::set_max_level(LevelFilter::Warn);
+
+        let _ =
+            Hash::new("password$123", "somesalt", "argon2i").unwrap();
+        assert!(logger.messages.lock().unwrap().is_empty());
+
+        let stored = Hash::new("password123", "somesalt", "argon2i")
+            .unwrap()
+            .to_phc_string()
+            .unwrap();
+        let _ = Hash::new(&stored, "somesalt", "argon2i").unwrap();
+        let _ = Hash::new("$2b$04$abcdefghij", "somesalt", "scrypt")
+            .unwrap();
+
+        let messages = logger.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].contains("PHC string"));
+        assert!(!messages[0].contains(&stored));
+    }
+}
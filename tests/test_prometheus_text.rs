@@ -0,0 +1,51 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the Prometheus text exporter, run with
+//! `--features prometheus-text`.
+
+#[cfg(all(test, feature = "prometheus-text"))]
+mod tests {
+    use hsh::models::hash::Hash;
+    use std::collections::HashMap;
+
+    // Reads the sample lines of the exposition into name -> value.
+    fn samples(text: &str) -> HashMap<String, u64> {
+        text.lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(|line| {
+                let (name, value) = line.split_once(' ').unwrap();
+                (name.to_string(), value.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_metrics_text_counts_operations() {
+        let text = hsh::metrics_text();
+        for name in [
+            "hsh_hashes_total",
+            "hsh_verifications_total",
+            "hsh_verification_failures_total",
+        ] {
+            assert!(
+                text.contains(&format!("# TYPE {} counter\n", name))
+            );
+        }
+
+        let before = samples(&text);
+        let hash =
+            Hash::new("password123", "somesalt", "scrypt").unwrap();
+        assert_eq!(hash.verify("password123"), Ok(true));
+        assert_eq!(hash.verify("password456"), Ok(false));
+        let after = samples(&hsh::metrics_text());
+
+        let delta = |name: &str| after[name] - before[name];
+        assert_eq!(delta("hsh_hashes_total"), 1);
+        assert_eq!(delta("hsh_verifications_total"), 2);
+        assert_eq!(delta("hsh_verification_failures_total"), 1);
+
+        // Nothing but counter names and numbers.
+        assert!(!hsh::metrics_text().contains("somesalt"));
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests that verifying a password writes nothing to stdout.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::hash::Hash;
+    use std::process::Command;
+
+    const PASSWORD: &str = "quiet-password-123";
+    const SALT: &str = "quiet-salt";
+
+    // Set in the child process that performs the verifications.
+    const CHILD_ENV: &str = "HSH_QUIET_VERIFY_CHILD";
+
+    #[test]
+    fn test_verify_prints_nothing() {
+        if std::env::var_os(CHILD_ENV).is_some() {
+            for algo in ["argon2i", "bcrypt", "scrypt"] {
+                let hash = Hash::new(PASSWORD, SALT, algo).unwrap();
+                assert_eq!(hash.verify(PASSWORD), Ok(true));
+                assert_eq!(hash.verify("wrongpassword"), Ok(false));
+            }
+            return;
+        }
+
+        // The test harness captures stdout in-process, so the
+        // verifications run in a child with capturing turned off.
+        let output = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "tests::test_verify_prints_nothing",
+                "--nocapture",
+            ])
+            .env(CHILD_ENV, "1")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        // Only the harness's own lines may appear.
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        for line in stdout.lines().filter(|line| !line.is_empty()) {
+            assert!(
+                line.starts_with("running ")
+                    || line.starts_with("test ")
+                    || line.starts_with("test result: "),
+                "unexpected output: {}",
+                line
+            );
+        }
+        assert!(!stdout.contains(PASSWORD));
+        assert!(!stdout.contains(SALT));
+    }
+}
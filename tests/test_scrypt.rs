@@ -1,6 +1,8 @@
 // Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Tests for the Scrypt hashing algorithm.
+
 #[cfg(test)]
 mod tests {
     use hsh::models::hash_algorithm::HashingAlgorithm;
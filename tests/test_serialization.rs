@@ -0,0 +1,145 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Property tests for the round trip of every serialization format of
+//! the `Hash` struct: PHC strings, JSON, the binary layout and stored
+//! components.
+
+#[cfg(test)]
+mod tests {
+    use base64::{
+        alphabet, engine::general_purpose::GeneralPurpose,
+        engine::GeneralPurposeConfig, Engine as _,
+    };
+    use hsh::models::{
+        hash::{Hash, HashBuilder},
+        hash_algorithm::HashAlgorithm,
+        params::Params,
+        stored_credential::StoredCredential,
+    };
+    use proptest::prelude::*;
+
+    /// The base64 engine of the salt and digest in Bcrypt hashes.
+    const BCRYPT_B64: GeneralPurpose = GeneralPurpose::new(
+        &alphabet::BCRYPT,
+        GeneralPurposeConfig::new().with_encode_padding(false),
+    );
+
+    prop_compose! {
+        /// Generates an Argon2i or Argon2id hash with valid parameters,
+        /// salt and digest lengths.
+        fn argon2_hash()(
+            algorithm in prop_oneof![
+                Just(HashAlgorithm::Argon2i),
+                Just(HashAlgorithm::Argon2id),
+            ],
+            version in prop_oneof![Just(0x10u32), Just(0x13u32)],
+            p_cost in 1u32..=8,
+            extra_m_cost in 0u32..=65536,
+            t_cost in 1u32..=10,
+            salt in prop::collection::vec(any::<u8>(), 8..=32),
+            digest in prop::collection::vec(any::<u8>(), 12..=64),
+        ) -> Hash {
+            HashBuilder::new()
+                .hash(digest)
+                .salt(salt)
+                .algorithm(algorithm)
+                .params(Params::Argon2 {
+                    version,
+                    m_cost: 8 * p_cost + extra_m_cost,
+                    t_cost,
+                    p_cost,
+                })
+                .build()
+                .unwrap()
+        }
+    }
+
+    prop_compose! {
+        /// Generates a Bcrypt hash from a random revision, cost, salt
+        /// and digest, parsed as a stored `$2b$...` string would be.
+        fn bcrypt_hash()(
+            revision in prop_oneof![Just("2a"), Just("2b"), Just("2y")],
+            cost in 4u32..=31,
+            salt in any::<[u8; 16]>(),
+            digest in any::<[u8; 23]>(),
+        ) -> Hash {
+            let mcf = format!(
+                "${}${:02}${}{}",
+                revision,
+                cost,
+                BCRYPT_B64.encode(salt),
+                BCRYPT_B64.encode(digest)
+            );
+            Hash::from_string(&mcf).unwrap()
+        }
+    }
+
+    prop_compose! {
+        /// Generates a Scrypt hash with valid parameters, salt and
+        /// digest lengths.
+        fn scrypt_hash()(
+            log_n in 1u8..=20,
+            r in 1u32..=16,
+            p in 1u32..=4,
+            salt in prop::collection::vec(any::<u8>(), 8..=32),
+            digest in prop::collection::vec(any::<u8>(), 10..=64),
+        ) -> Hash {
+            HashBuilder::new()
+                .hash(digest)
+                .salt(salt)
+                .algorithm(HashAlgorithm::Scrypt)
+                .params(Params::Scrypt { log_n, r, p })
+                .build()
+                .unwrap()
+        }
+    }
+
+    /// Generates a hash of any algorithm.
+    fn any_hash() -> impl Strategy<Value = Hash> {
+        prop_oneof![argon2_hash(), bcrypt_hash(), scrypt_hash()]
+    }
+
+    proptest! {
+        #[test]
+        fn test_phc_round_trip(hash in any_hash()) {
+            let phc = hash.to_phc_string().unwrap();
+            prop_assert_eq!(&Hash::from_string(&phc).unwrap(), &hash);
+
+            let preserved = Hash::from_string_preserving(&phc).unwrap();
+            prop_assert_eq!(preserved.original_phc(), Some(phc.as_str()));
+            prop_assert_eq!(preserved.to_phc_string().unwrap(), phc);
+        }
+
+        #[test]
+        fn test_json_round_trip(hash in any_hash()) {
+            let json = serde_json::to_string(&hash).unwrap();
+            prop_assert_eq!(&serde_json::from_str::<Hash>(&json).unwrap(), &hash);
+            prop_assert_eq!(&Hash::parse(&json).unwrap(), &hash);
+        }
+
+        #[test]
+        fn test_binary_round_trip(hash in any_hash()) {
+            let bytes = hash.to_bytes().unwrap();
+            prop_assert_eq!(&Hash::from_bytes(&bytes).unwrap(), &hash);
+        }
+
+        #[test]
+        fn test_components_round_trip(hash in any_hash()) {
+            let credential = StoredCredential::from(&hash);
+            let json = serde_json::to_string(&credential).unwrap();
+            let restored: StoredCredential =
+                serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(&restored, &credential);
+
+            let rebuilt = Hash::reconstruct(
+                &restored.hash,
+                &restored.salt,
+                restored.algorithm,
+                restored.params,
+            )
+            .unwrap();
+            prop_assert_eq!(&rebuilt, &hash);
+        }
+    }
+}
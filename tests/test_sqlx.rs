@@ -0,0 +1,55 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the SQLx integration, run with `--features sqlx`.
+
+#[cfg(all(test, feature = "sqlx"))]
+mod tests {
+    use hsh::models::hash::Hash;
+    use sqlx::{Connection, SqliteConnection};
+
+    #[tokio::test]
+    async fn test_hash_round_trips_through_text_column() {
+        let mut conn =
+            SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        let _ =
+            sqlx::query("CREATE TABLE users (password TEXT NOT NULL)")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+
+        for algo in ["argon2i", "bcrypt", "scrypt"] {
+            let hash =
+                Hash::new("password123", "somesalt", algo).unwrap();
+            let _ =
+                sqlx::query("INSERT INTO users (password) VALUES (?)")
+                    .bind(&hash)
+                    .execute(&mut conn)
+                    .await
+                    .unwrap();
+
+            let stored: String =
+                sqlx::query_scalar("SELECT password FROM users")
+                    .fetch_one(&mut conn)
+                    .await
+                    .unwrap();
+            assert_eq!(stored, hash.to_phc_string().unwrap());
+
+            let loaded: Hash =
+                sqlx::query_scalar("SELECT password FROM users")
+                    .fetch_one(&mut conn)
+                    .await
+                    .unwrap();
+            assert_eq!(
+                loaded.to_phc_string().unwrap(),
+                hash.to_phc_string().unwrap()
+            );
+            assert!(loaded.verify("password123").unwrap());
+
+            let _ = sqlx::query("DELETE FROM users")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+    }
+}
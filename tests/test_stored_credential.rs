@@ -0,0 +1,60 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the `StoredCredential` struct.
+
+#[cfg(test)]
+mod tests {
+    use hsh::models::{
+        error::HshError, hash::Hash, hash_algorithm::HashAlgorithm,
+        params::Params, stored_credential::StoredCredential,
+    };
+
+    #[test]
+    fn test_serde_round_trip() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new("password123", "somesalt", "argon2id").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        for hash in &hashes {
+            let credential = StoredCredential::from(hash);
+            assert_eq!(credential.algorithm, hash.algorithm);
+            assert_eq!(credential.params, hash.parameters());
+
+            let json = serde_json::to_string(&credential).unwrap();
+            let restored: StoredCredential =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, credential);
+        }
+    }
+
+    #[test]
+    fn test_verify_stored() {
+        let hashes = [
+            Hash::new("password123", "somesalt", "argon2i").unwrap(),
+            Hash::new_bcrypt("password123", 4).unwrap(),
+            Hash::new("password123", "somesalt", "scrypt").unwrap(),
+        ];
+        for hash in &hashes {
+            let credential = StoredCredential::from(hash);
+            assert_eq!(
+                Hash::verify_stored(&credential, "password123"),
+                Ok(true)
+            );
+            assert_eq!(
+                Hash::verify_stored(&credential, "wrongpassword"),
+                Ok(false)
+            );
+        }
+
+        // The parameters must belong to the algorithm.
+        let mut credential = StoredCredential::from(&hashes[0]);
+        credential.params = Params::default_for(HashAlgorithm::Scrypt);
+        assert!(matches!(
+            Hash::verify_stored(&credential, "password123"),
+            Err(HshError::InvalidParams(_))
+        ));
+    }
+}
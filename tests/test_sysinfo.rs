@@ -0,0 +1,31 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the system memory recommendation, run with
+//! `--features sysinfo`.
+
+#[cfg(all(test, feature = "sysinfo"))]
+mod tests {
+    use hsh::models::{
+        hash_algorithm::HashAlgorithm,
+        hash_options::{
+            HashOptions, RECOMMENDED_MAX_M_COST, RECOMMENDED_MIN_M_COST,
+        },
+        params::Params,
+    };
+
+    #[test]
+    fn test_recommended_from_system_stays_within_bounds() {
+        let options = HashOptions::recommended_from_system();
+
+        assert_eq!(options.algorithm, HashAlgorithm::Argon2i);
+        match options.params {
+            Params::Argon2 { m_cost, .. } => {
+                assert!((RECOMMENDED_MIN_M_COST
+                    ..=RECOMMENDED_MAX_M_COST)
+                    .contains(&m_cost))
+            }
+            other => panic!("unexpected parameters: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for Unicode password normalization, run with
+//! `--features unicode`.
+
+#[cfg(all(test, feature = "unicode"))]
+mod tests {
+    use hsh::models::hash::Hash;
+
+    // "crème brûlée" with precomposed characters (NFC) and with base
+    // letters followed by combining accents (NFD).
+    const NFC: &str = "cr\u{e8}me br\u{fb}l\u{e9}e";
+    const NFD: &str = "cre\u{300}me bru\u{302}le\u{301}e";
+
+    #[test]
+    fn test_nfc_and_nfd_verify_against_each_other() {
+        assert_ne!(NFC, NFD);
+
+        let nfc =
+            Hash::new_normalized(NFC, "somesalt", "argon2i").unwrap();
+        assert_eq!(nfc.verify_normalized(NFD), Ok(true));
+
+        let nfd =
+            Hash::new_normalized(NFD, "somesalt", "argon2i").unwrap();
+        assert_eq!(nfd.verify_normalized(NFC), Ok(true));
+        assert_eq!(nfc, nfd);
+    }
+
+    #[test]
+    fn test_normalization_must_match() {
+        let plain = Hash::new(NFD, "somesalt", "argon2i").unwrap();
+
+        assert_eq!(plain.verify(NFD), Ok(true));
+        assert_eq!(plain.verify_normalized(NFD), Ok(false));
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright © 2023-2024 Hash (HSH) library. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the Bcrypt warning, run with `--features warn-weak`.
+
+#[cfg(all(test, feature = "warn-weak"))]
+mod tests {
+    use hsh::models::hash::Hash;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    // Logger collecting warnings so the test can inspect them
+    struct CaptureLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl Log for CaptureLogger {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= Level::Warn
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            if self.enabled(record.metadata()) {
+                self.messages
+                    .lock()
+                    .unwrap()
+                    .push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_bcrypt_warns_about_72_byte_limit() {
+        let logger: &'static CaptureLogger =
+            Box::leak(Box::new(CaptureLogger {
+                messages: Mutex::new(Vec::new()),
+            }));
+        log::set_logger(logger).unwrap();
+        log::set_max_level(LevelFilter::Warn);
+
+        let password = "secret-password-123";
+        let _ = Hash::new(password, "somesalt", "bcrypt").unwrap();
+
+        let messages = logger.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("72 bytes"));
+        assert!(!messages[0].contains(password));
+    }
+}